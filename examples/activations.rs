@@ -0,0 +1,73 @@
+use ferrograd::{
+    engine::{ActvFn, Value},
+    loss::{HingeLoss, Loss},
+    metrics::BinaryAccuracy,
+    nn::{optim::Adam, LayerActvFn, Module, MultiLayerPerceptron},
+    utils::{manual_seed, read_csv},
+};
+
+// Squareplus, a smooth approximation of ReLU, (x + √(x² + 4)) / 2.
+const SQUAREPLUS: ActvFn = ActvFn::Custom {
+    name: "squareplus",
+    forward: |x| (x + (x * x + 4.0).sqrt()) / 2.0,
+    derivative: |x| (1.0 + x / (x * x + 4.0).sqrt()) / 2.0,
+};
+
+fn main() {
+    manual_seed(42);
+
+    let (xs, ys) = read_csv("data/moons_data.csv", &[0, 1], &[2], 1);
+
+    let prelu = Some(LayerActvFn::from(ActvFn::PReLU(0.25)));
+    let model = MultiLayerPerceptron::with_actv_fns(
+        2,
+        vec![16, 16, 1],
+        vec![prelu, Some(SQUAREPLUS.into()), None],
+    );
+    println!("{}", model);
+
+    let slopes = || -> Vec<Value> {
+        model
+            .named_parameters()
+            .into_iter()
+            .filter(|(name, _)| name.ends_with("slope"))
+            .map(|(_, slope)| slope)
+            .collect()
+    };
+    println!("Learnable PReLU slopes: {}\n", slopes().len());
+
+    let mut optim = Adam::new(model.parameters(), 0.05, 0.9, 0.999, 1e-8);
+    let loss = HingeLoss::new();
+    let accuracy = BinaryAccuracy::new(0.0);
+
+    for k in 0..50 {
+        let ypred = model.forward(&xs);
+        let data_loss = loss.loss(&ypred, &ys);
+
+        optim.zero_grad();
+        data_loss.backward();
+        optim.step();
+
+        if k % 10 == 9 {
+            let acc = accuracy.compute(&ypred, &ys);
+            println!(
+                "step {} - loss {:.3}, accuracy {:.2}%",
+                k,
+                data_loss.borrow().data,
+                acc * 100.0
+            );
+        }
+    }
+
+    let slopes: Vec<String> = slopes()
+        .iter()
+        .take(4)
+        .map(|slope| format!("{:.3}", slope.borrow().data))
+        .collect();
+    println!("\nFirst PReLU slopes: {}", slopes.join(", "));
+
+    let x = Value::new(-1.5).with_name('x');
+    let a = Value::new(0.25).with_name('a');
+    println!("\n{}", x.prelu(&a).expr());
+    println!("{}", x.activate(SQUAREPLUS).expr().derivative('x'));
+}
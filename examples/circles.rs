@@ -1,22 +1,24 @@
 use ferrograd::{
     engine::{ActvFn, Value},
-    loss::BinaryCrossEntropyLoss,
+    loss::{BinaryCrossEntropyWithLogitsLoss, Loss},
     metrics::BinaryAccuracy,
     nn::{
         optim::{l2_regularization, Adam},
-        sigmoid, MultiLayerPerceptron,
+        sigmoid, Module, MultiLayerPerceptron,
     },
-    utils::read_csv,
+    utils::{manual_seed, read_csv},
 };
 
 fn main() {
+    manual_seed(42);
+
     let (xs, ys) = read_csv("data/circles_data.csv", &[0, 1], &[2], 1);
 
     let model = MultiLayerPerceptron::new(2, vec![16, 16, 1], ActvFn::ReLU);
     println!("Model: {:#?}\n", model);
 
     let mut optim = Adam::new(model.parameters(), 0.1, 0.9, 0.999, 1e-8);
-    let loss = BinaryCrossEntropyLoss::new();
+    let loss = BinaryCrossEntropyWithLogitsLoss::new();
     let accuracy = BinaryAccuracy::new(0.5);
 
     println!(
@@ -25,10 +27,10 @@ fn main() {
     );
 
     for k in 0..100 {
-        let ypred = model.forward(&xs);
-        let ypred = sigmoid(&ypred);
+        let logits = model.forward(&xs);
+        let ypred = sigmoid(&logits);
 
-        let data_loss = loss.loss(&ypred, &ys);
+        let data_loss = loss.loss(&logits, &ys);
         let reg_loss = l2_regularization(0.0001, model.parameters());
         let total_loss = data_loss + reg_loss;
 
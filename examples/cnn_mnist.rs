@@ -0,0 +1,94 @@
+use ferrograd::{
+    engine::{ActvFn, Value},
+    loss::{one_hot, ClassificationLoss, CrossEntropyLoss},
+    metrics::BinaryAccuracy,
+    nn::{optim::Adam, softmax, Activation, Conv2d, Flatten, Layer, MaxPool2d, Module, Sequential},
+    utils::manual_seed,
+};
+
+fn main() {
+    manual_seed(42);
+
+    let batch_size = 20;
+    let mnist = rust_mnist::Mnist::new("data/mnist/");
+
+    // 1×28×28 → 4×12×12 → 4×6×6 → 10
+    let conv = Conv2d::new(1, 4, 5, 2, 0, (28, 28));
+    let (c, h, w) = conv.output_shape();
+    let pool = MaxPool2d::new(c as u32, 2, 2, (h as u32, w as u32));
+    let shape = pool.output_shape();
+
+    let mut model = Sequential::new()
+        .push(conv)
+        .push(Activation::new(ActvFn::ReLU))
+        .push(pool)
+        .push(Flatten::new(shape))
+        .push(Layer::new((shape.0 * shape.1 * shape.2) as u32, 10, None));
+    println!("{}", model);
+
+    let mut optim = Adam::new(model.parameters(), 0.01, 0.9, 0.999, 1e-8);
+    let loss = CrossEntropyLoss::new();
+    let accuracy = BinaryAccuracy::new(0.5);
+
+    for k in 0..100 {
+        let start = (k % 50) * batch_size;
+        let end = start + batch_size;
+
+        let xtrain = images_to_features(&mnist.train_data[start..end]);
+        let labels: Vec<usize> = mnist.train_labels[start..end]
+            .iter()
+            .map(|digit| *digit as usize)
+            .collect();
+        let ytrain = one_hot(&labels, 10);
+
+        let ypred = softmax(&model.forward(&xtrain));
+        let data_loss = loss.loss_from_labels(&ypred, &labels);
+
+        optim.zero_grad();
+        data_loss.backward();
+        optim.step();
+
+        let acc = accuracy.compute(&ypred, &ytrain);
+        println!(
+            "step {} - loss {:.3}, accuracy {:.2}%",
+            k,
+            data_loss.borrow().data,
+            acc * 100.0
+        );
+    }
+
+    let test_samples = 100;
+    let xtest = images_to_features(&mnist.test_data[..test_samples]);
+
+    println!("Testing");
+    model.eval();
+    let ypred = softmax(&model.forward(&xtest));
+
+    let correct = ypred
+        .iter()
+        .zip(&mnist.test_labels[..test_samples])
+        .filter(|(ypred_i, label)| {
+            let argmax = ypred_i
+                .iter()
+                .enumerate()
+                .max_by_key(|(_, v)| *v)
+                .map(|(ind, _)| ind)
+                .expect("Error in prediction");
+            **label as usize == argmax
+        })
+        .count();
+
+    println!("Correct predictions: {}/{}", correct, test_samples);
+}
+
+// Images are 1×28×28, flattened row by row as Conv2d expects.
+fn images_to_features(imgvec: &[[u8; 784]]) -> Vec<Vec<Value>> {
+    imgvec
+        .iter()
+        .map(|img| {
+            img.iter()
+                .map(|pix| Value::new(*pix as f64 / 255.0))
+                .collect()
+        })
+        .collect()
+}
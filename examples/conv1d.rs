@@ -0,0 +1,75 @@
+use ferrograd::{
+    engine::{ActvFn, Value},
+    loss::{BinaryCrossEntropyLoss, Loss},
+    metrics::BinaryAccuracy,
+    nn::{optim::Adam, Activation, Conv1d, GlobalAvgPool1d, Layer, MaxPool1d, Module, Sequential},
+    utils::{manual_seed, with_rng},
+};
+use rand::Rng;
+use std::f64::consts::PI;
+
+const LEN: u32 = 32;
+
+fn main() {
+    manual_seed(42);
+
+    // Signals of 1×32 → 4×32 → 4×16 → 4 → 1
+    let conv = Conv1d::new(1, 4, 5, 1, 4, 2, LEN);
+    let (c, len) = conv.output_shape();
+    let pool = MaxPool1d::new(c as u32, 2, 2, len as u32);
+    let (c, len) = pool.output_shape();
+
+    let model = Sequential::new()
+        .push(conv)
+        .push(Activation::new(ActvFn::ReLU))
+        .push(pool)
+        .push(GlobalAvgPool1d::new(c as u32, len as u32))
+        .push(Layer::new(c as u32, 1, None))
+        .push(Activation::new(ActvFn::Sigmoid));
+    println!("{}", model);
+
+    let (xs, ys) = signals(40);
+
+    let mut optim = Adam::new(model.parameters(), 0.05, 0.9, 0.999, 1e-8);
+    let loss = BinaryCrossEntropyLoss::new();
+    let accuracy = BinaryAccuracy::new(0.5);
+
+    for k in 0..50 {
+        let ypred = model.forward(&xs);
+        let data_loss = loss.loss(&ypred, &ys);
+
+        optim.zero_grad();
+        data_loss.backward();
+        optim.step();
+
+        let acc = accuracy.compute(&ypred, &ys);
+        println!(
+            "step {} - loss {:.3}, accuracy {:.2}%",
+            k,
+            data_loss.borrow().data,
+            acc * 100.0
+        );
+    }
+}
+
+// Slow sine waves are labelled 0 and fast ones 1, with random phase and noise.
+fn signals(n: usize) -> (Vec<Vec<Value>>, Vec<Vec<Value>>) {
+    with_rng(|rng| {
+        (0..n)
+            .map(|i| {
+                let label = (i % 2) as f64;
+                let freq = if label == 0.0 { 1.0 } else { 4.0 };
+                let phase = rng.gen_range(0.0..2.0 * PI);
+
+                let x = (0..LEN)
+                    .map(|t| {
+                        let t = t as f64 / LEN as f64;
+                        let noise = rng.gen_range(-0.2..0.2);
+                        Value::new((2.0 * PI * freq * t + phase).sin() + noise)
+                    })
+                    .collect();
+                (x, vec![Value::new(label)])
+            })
+            .unzip()
+    })
+}
@@ -0,0 +1,67 @@
+use ferrograd::{
+    engine::Value,
+    loss::{ClassificationLoss, CrossEntropyLoss},
+    nn::{optim::Adam, softmax, Embedding, Layer, Module},
+    utils::{manual_seed, with_rng},
+};
+use rand::Rng;
+
+const TEXT: &str = "the cat sat on the mat. the dog sat on the log. the cat and the dog ran.";
+
+fn main() {
+    manual_seed(42);
+
+    // Character-level bigram model, predicting the next character from the current one.
+    let mut vocab: Vec<char> = TEXT.chars().collect();
+    vocab.sort();
+    vocab.dedup();
+    let index = |c: char| vocab.iter().position(|&v| v == c).expect("Unknown char");
+
+    let chars: Vec<usize> = TEXT.chars().map(index).collect();
+    let (inputs, targets) = (&chars[..chars.len() - 1], &chars[1..]);
+
+    let embedding = Embedding::new(vocab.len() as u32, 8);
+    let head = Layer::new(8, vocab.len() as u32, None);
+    println!("{}\n{}\n", embedding, head);
+
+    let mut params = embedding.parameters();
+    params.extend(head.parameters());
+    let mut optim = Adam::new(params, 0.05, 0.9, 0.999, 1e-8).with_sparse_updates();
+    let loss = CrossEntropyLoss::new();
+
+    for k in 0..50 {
+        // Only the rows of the characters in the batch receive gradients.
+        let start = (k * 16) % (inputs.len() - 16);
+        let xs = embedding.lookup(&inputs[start..start + 16]);
+        let ypred = softmax(&head.forward(&xs));
+        let data_loss = loss.loss_from_labels(&ypred, &targets[start..start + 16]);
+
+        optim.zero_grad();
+        data_loss.backward();
+        optim.step();
+
+        println!("step {} - loss {:.3}", k, data_loss.borrow().data);
+    }
+
+    let mut c = index('t');
+    let mut sample = String::from("t");
+    for _ in 0..40 {
+        let probs = softmax(&head.forward(&embedding.lookup(&[c])));
+        c = sample_index(&probs[0]);
+        sample.push(vocab[c]);
+    }
+    println!("\nSample: {}", sample);
+}
+
+fn sample_index(probs: &[Value]) -> usize {
+    let r: f64 = with_rng(|rng| rng.gen());
+    let mut cumulative = 0.0;
+
+    for (i, p) in probs.iter().enumerate() {
+        cumulative += p.borrow().data;
+        if r < cumulative {
+            return i;
+        }
+    }
+    probs.len() - 1
+}
@@ -0,0 +1,21 @@
+use ferrograd::engine::Value;
+
+fn main() {
+    let a = Value::new(5.6).with_name('a');
+    let b = Value::new(10.8).with_name('b');
+
+    let c = Value::new(-15.12).with_name('c');
+    let d = Value::new(2.5).with_name('d');
+
+    let e = (a + b) / 50.0;
+    let f = (d - c) * 5.5625;
+    let g = (e * f).relu();
+
+    let expr = g.expr();
+    println!("infix: {}", expr);
+    println!("latex: {}\n", expr.to_latex());
+
+    let dg_da = expr.derivative('a');
+    println!("∂g/∂a: {}", dg_da);
+    println!("latex: {}", dg_da.to_latex());
+}
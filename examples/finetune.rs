@@ -0,0 +1,76 @@
+use ferrograd::{
+    engine::{ActvFn, Value},
+    loss::{BinaryCrossEntropyLoss, Loss},
+    metrics::BinaryAccuracy,
+    nn::{optim::Adam, sigmoid, Module, MultiLayerPerceptron},
+    utils::{manual_seed, read_csv},
+};
+
+fn main() {
+    manual_seed(42);
+
+    let (moons_x, moons_y) = read_csv("data/moons_data.csv", &[0, 1], &[2], 1);
+    // Moons labels are -1 and 1, circles labels are 0 and 1.
+    let moons_y: Vec<Vec<Value>> = moons_y
+        .iter()
+        .map(|y| vec![Value::new((y[0].borrow().data + 1.0) / 2.0)])
+        .collect();
+    let (circles_x, circles_y) = read_csv("data/circles_data.csv", &[0, 1], &[2], 1);
+
+    let model = MultiLayerPerceptron::new(2, vec![16, 16, 1], ActvFn::ReLU);
+    println!("{}", model);
+
+    println!("Training on moons");
+    train(&model, &moons_x, &moons_y);
+
+    // Freeze the hidden layers, and retrain the output layer only.
+    let n = model.layers().len();
+    model.layers()[..n - 1]
+        .iter()
+        .for_each(|layer| layer.freeze());
+
+    let frozen: Vec<f64> = model.layers()[0]
+        .parameters()
+        .iter()
+        .map(|p| p.borrow().data)
+        .collect();
+
+    println!(
+        "\nFine-tuning {} of {} parameters on circles",
+        model.trainable_parameters().len(),
+        model.parameters().len()
+    );
+    train(&model, &circles_x, &circles_y);
+
+    let unchanged = model.layers()[0]
+        .parameters()
+        .iter()
+        .zip(frozen)
+        .all(|(p, data)| p.borrow().data == data);
+    println!("\nFrozen layers unchanged: {}", unchanged);
+}
+
+fn train(model: &MultiLayerPerceptron, xs: &[Vec<Value>], ys: &[Vec<Value>]) {
+    let mut optim = Adam::new(model.trainable_parameters(), 0.05, 0.9, 0.999, 1e-8);
+    let loss = BinaryCrossEntropyLoss::new();
+    let accuracy = BinaryAccuracy::new(0.5);
+
+    for k in 0..50 {
+        let ypred = sigmoid(&model.forward(xs));
+        let data_loss = loss.loss(&ypred, ys);
+
+        optim.zero_grad();
+        data_loss.backward();
+        optim.step();
+
+        if k % 10 == 9 {
+            let acc = accuracy.compute(&ypred, ys);
+            println!(
+                "step {} - loss {:.3}, accuracy {:.2}%",
+                k,
+                data_loss.borrow().data,
+                acc * 100.0
+            );
+        }
+    }
+}
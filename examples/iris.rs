@@ -1,17 +1,25 @@
 use ferrograd::{
     engine::{ActvFn, Value},
-    loss::CrossEntropyLoss,
+    loss::{CrossEntropyLoss, Loss},
     metrics::BinaryAccuracy,
     nn::{
         optim::{l2_regularization, Adam},
-        softmax, MultiLayerPerceptron,
+        LayerActvFn, Module, MultiLayerPerceptron,
     },
 };
 
 fn main() {
     let (xs, ys) = read_iris_csv();
 
-    let model = MultiLayerPerceptron::new(4, vec![16, 16, 3], ActvFn::ReLU);
+    let model = MultiLayerPerceptron::with_actv_fns(
+        4,
+        vec![16, 16, 3],
+        vec![
+            Some(ActvFn::ReLU.into()),
+            Some(ActvFn::ReLU.into()),
+            Some(LayerActvFn::Softmax),
+        ],
+    );
     println!("Model - \n{}", model);
     println!("Number of parameters = {}\n", model.parameters().len());
 
@@ -25,7 +33,7 @@ fn main() {
     );
 
     for k in 0..100 {
-        let ypred = softmax(&model.forward(&xs));
+        let ypred = model.forward(&xs);
 
         let data_loss = loss.loss(&ypred, &ys);
         let reg_loss = l2_regularization(0.0001, model.parameters());
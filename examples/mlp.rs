@@ -1,6 +1,6 @@
 use ferrograd::{
     engine::{ActvFn, Value},
-    nn::MultiLayerPerceptron,
+    nn::{Module, MultiLayerPerceptron},
 };
 
 fn main() {
@@ -1,15 +1,17 @@
 use ferrograd::{
     engine::{ActvFn, Value},
-    loss::HingeLoss,
+    loss::{HingeLoss, Loss},
     metrics::BinaryAccuracy,
     nn::{
         optim::{l2_regularization, SGD},
-        MultiLayerPerceptron,
+        Module, MultiLayerPerceptron,
     },
-    utils::read_csv,
+    utils::{manual_seed, read_csv},
 };
 
 fn main() {
+    manual_seed(42);
+
     let (xs, ys) = read_csv("data/moons_data.csv", &[0, 1], &[2], 1);
 
     let model = MultiLayerPerceptron::new(2, vec![16, 16, 1], ActvFn::ReLU);
@@ -1,6 +1,6 @@
 use ferrograd::{
     engine::{ActvFn, Value},
-    nn::Neuron,
+    nn::{Module, Neuron},
 };
 
 fn main() {
@@ -12,7 +12,7 @@ fn main() {
 
     println!("{}\n", n);
 
-    let y = &n.forward(&x)[0];
+    let y = &n.forward(&x)[0][0];
     println!("Forward pass: \n{}", y.tree());
 
     y.backward();
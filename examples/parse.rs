@@ -0,0 +1,25 @@
+use ferrograd::engine::{parse, Value};
+use std::collections::HashMap;
+
+fn main() {
+    let x = Value::new(1.5).with_name('x');
+    let y = Value::new(-0.5).with_name('y');
+    let z = Value::new(2.0).with_name('z');
+
+    let bindings = HashMap::from([("x", x.clone()), ("y", y.clone()), ("z", z.clone())]);
+
+    let f = parse("x^2 + exp(-y) * relu(z)", &bindings).unwrap();
+    println!("f = {}", f.expr());
+    println!("f.data = {:.4}", f.borrow().data);
+
+    f.backward();
+    println!("x.grad = {:.4}", x.borrow().grad);
+    println!("y.grad = {:.4}", y.borrow().grad);
+    println!("z.grad = {:.4}\n", z.borrow().grad);
+
+    for src in ["x^y", "2 * (x + w)", "tanh(x", "x $ y"] {
+        if let Err(err) = parse(src, &bindings) {
+            println!("{:<12} → {}", src, err);
+        }
+    }
+}
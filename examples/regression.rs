@@ -0,0 +1,101 @@
+use ferrograd::{
+    engine::{ActvFn, Value},
+    loss::{
+        GaussianNLLLoss, HuberLoss, L1Loss, LogCoshLoss, Loss, MeanSquareErrorLoss, QuantileLoss,
+        SmoothL1Loss,
+    },
+    nn::{optim::Adam, Module, MultiLayerPerceptron},
+    utils::{manual_seed, with_rng},
+};
+use rand::Rng;
+use std::f64::consts::PI;
+
+fn main() {
+    manual_seed(42);
+
+    let losses: Vec<(&str, Box<dyn Loss>)> = vec![
+        ("mse", Box::new(MeanSquareErrorLoss::new())),
+        ("l1", Box::new(L1Loss::new())),
+        ("huber", Box::new(HuberLoss::new(0.5))),
+        ("smooth_l1", Box::new(SmoothL1Loss::new(0.5))),
+        ("log_cosh", Box::new(LogCoshLoss::new())),
+        ("quantile(0.5)", Box::new(QuantileLoss::new(0.5))),
+    ];
+
+    // Sine wave with noise, and a tenth of the targets replaced by outliers.
+    let (xs, ys) = noisy_sine(100);
+
+    println!("MSE against the clean sine wave");
+    for (name, loss) in &losses {
+        let model = MultiLayerPerceptron::new(1, vec![16, 16, 1], ActvFn::Tanh);
+        fit(&model, loss.as_ref(), &xs, &ys, |out| out.to_vec());
+        println!(
+            "{:>14} {:.4}",
+            name,
+            clean_error(|x| model.forw(x)[0].clone())
+        );
+    }
+
+    // The model predicts a mean and a variance, kept positive with softplus.
+    let model = MultiLayerPerceptron::new(1, vec![16, 16, 2], ActvFn::Tanh);
+    let nll = GaussianNLLLoss::new();
+    let mean_var = |out: &[Value]| vec![out[0].clone(), out[1].softplus()];
+    fit(&model, &nll, &xs, &ys, mean_var);
+    println!(
+        "{:>14} {:.4}",
+        "gaussian_nll",
+        clean_error(|x| model.forw(x)[0].clone())
+    );
+}
+
+fn fit<F>(
+    model: &MultiLayerPerceptron,
+    loss: &dyn Loss,
+    xs: &[Vec<Value>],
+    ys: &[Vec<Value>],
+    head: F,
+) where
+    F: Fn(&[Value]) -> Vec<Value>,
+{
+    let mut optim = Adam::new(model.parameters(), 0.02, 0.9, 0.999, 1e-8);
+
+    for _ in 0..150 {
+        let ypred: Vec<Vec<Value>> = model.forward(xs).iter().map(|out| head(out)).collect();
+        let data_loss = loss.loss(&ypred, ys);
+
+        optim.zero_grad();
+        data_loss.backward();
+        optim.step();
+    }
+}
+
+fn noisy_sine(n: usize) -> (Vec<Vec<Value>>, Vec<Vec<Value>>) {
+    with_rng(|rng| {
+        (0..n)
+            .map(|_| {
+                let x = rng.gen_range(-1.0..1.0);
+                let y = if rng.gen_bool(0.1) {
+                    rng.gen_range(-4.0..4.0)
+                } else {
+                    (PI * x).sin() + rng.gen_range(-0.1..0.1)
+                };
+                (vec![Value::new(x)], vec![Value::new(y)])
+            })
+            .unzip()
+    })
+}
+
+fn clean_error<F>(predict: F) -> f64
+where
+    F: Fn(&[Value]) -> Value,
+{
+    let n = 50;
+    (0..n)
+        .map(|i| {
+            let x = -1.0 + 2.0 * i as f64 / (n - 1) as f64;
+            let y = predict(&[Value::new(x)]).borrow().data;
+            (y - (PI * x).sin()).powi(2)
+        })
+        .sum::<f64>()
+        / n as f64
+}
@@ -0,0 +1,84 @@
+use ferrograd::{
+    engine::ActvFn,
+    loss::{HingeLoss, Loss},
+    metrics::BinaryAccuracy,
+    nn::{optim::Adam, Activation, ConcatSkip, Init, Layer, Module, Residual, Sequential},
+    utils::{manual_seed, read_csv},
+};
+
+const DEPTH: usize = 12;
+
+fn main() {
+    manual_seed(42);
+
+    let (xs, ys) = read_csv("data/moons_data.csv", &[0, 1], &[2], 1);
+
+    // The same deep stack of layers, with and without residual connections.
+    for residual in [false, true] {
+        let model = build_model(residual);
+        println!(
+            "{} model, {} parameters",
+            if residual { "Residual" } else { "Plain" },
+            model.parameters().len()
+        );
+
+        let mut optim = Adam::new(model.parameters(), 0.01, 0.9, 0.999, 1e-8);
+        let loss = HingeLoss::new();
+        let accuracy = BinaryAccuracy::new(0.0);
+
+        for k in 0..50 {
+            let ypred = model.forward(&xs);
+            let data_loss = loss.loss(&ypred, &ys);
+
+            optim.zero_grad();
+            data_loss.backward();
+            optim.step();
+
+            if k % 10 == 9 {
+                let acc = accuracy.compute(&ypred, &ys);
+                println!(
+                    "step {} - loss {:.3}, accuracy {:.2}%",
+                    k,
+                    data_loss.borrow().data,
+                    acc * 100.0
+                );
+            }
+        }
+        println!();
+    }
+}
+
+fn build_model(residual: bool) -> Sequential {
+    // The input is concatenated with the first hidden layer, 2 → 14 + 2.
+    let mut model = Sequential::new().push(ConcatSkip::new(
+        Sequential::new()
+            .push(Layer::with_init(
+                2,
+                14,
+                None,
+                &Init::HeUniform,
+                &Init::Zeros,
+            ))
+            .push(Activation::new(ActvFn::Tanh)),
+    ));
+
+    for _ in 0..DEPTH {
+        let block = Sequential::new()
+            .push(Layer::with_init(
+                16,
+                16,
+                None,
+                &Init::XavierUniform,
+                &Init::Zeros,
+            ))
+            .push(Activation::new(ActvFn::Tanh));
+
+        model = if residual {
+            model.push(Residual::new(block))
+        } else {
+            model.push(block)
+        };
+    }
+
+    model.push(Layer::new(16, 1, None))
+}
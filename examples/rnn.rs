@@ -0,0 +1,52 @@
+use ferrograd::{
+    engine::Value,
+    loss::{Loss, MeanSquareErrorLoss},
+    nn::{optim::Adam, LSTMCell, Layer, Module, Recurrent},
+    utils::manual_seed,
+};
+
+fn main() {
+    manual_seed(42);
+
+    // Predict the next value of a sine wave, one value per time step.
+    let wave: Vec<f64> = (0..101).map(|t| (t as f64 * 0.3).sin()).collect();
+    let xs: Vec<Vec<Value>> = wave[..100].iter().map(|x| vec![Value::new(*x)]).collect();
+    let ys: Vec<Vec<Value>> = wave[1..].iter().map(|y| vec![Value::new(*y)]).collect();
+
+    let lstm = Recurrent::new(LSTMCell::new(1, 8));
+    let readout = Layer::new(8, 1, None);
+    println!("{}\n{}\n", lstm, readout);
+
+    let mut params = lstm.parameters();
+    params.extend(readout.parameters());
+    let mut optim = Adam::new(params, 0.02, 0.9, 0.999, 1e-8);
+    let loss = MeanSquareErrorLoss::new();
+
+    // Truncated BPTT, over chunks of 20 time steps. The state is carried over between
+    // chunks, but detached so that each backward pass stays within its chunk.
+    let chunk = 20;
+
+    for epoch in 0..30 {
+        let mut state = None;
+        let mut epoch_loss = 0.0;
+
+        for (xs_chunk, ys_chunk) in xs.chunks(chunk).zip(ys.chunks(chunk)) {
+            let (hs, last) = lstm.unroll(xs_chunk, state);
+            let ypred = readout.forward(&hs);
+            let data_loss = loss.loss(&ypred, ys_chunk);
+
+            optim.zero_grad();
+            data_loss.backward();
+            optim.step();
+
+            epoch_loss += data_loss.borrow().data;
+            state = Some(last.detach());
+        }
+
+        println!(
+            "epoch {} - loss {:.4}",
+            epoch,
+            epoch_loss / (xs.len() / chunk) as f64
+        );
+    }
+}
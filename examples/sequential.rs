@@ -0,0 +1,64 @@
+use ferrograd::{
+    engine::ActvFn,
+    loss::{BinaryCrossEntropyLoss, Loss},
+    metrics::BinaryAccuracy,
+    nn::{optim::Adam, Activation, Layer, LayerNorm, Module, Sequential},
+    utils::read_csv,
+};
+
+fn main() {
+    let (xs, ys) = read_csv("data/circles_data.csv", &[0, 1], &[2], 1);
+
+    let model = build_model();
+
+    println!("{}", model);
+    println!("Model: {:#?}\n", model);
+
+    let mut optim = Adam::new(model.parameters(), 0.05, 0.9, 0.999, 1e-8);
+    let loss = BinaryCrossEntropyLoss::new();
+    let accuracy = BinaryAccuracy::new(0.5);
+
+    for k in 0..50 {
+        let ypred = model.forward(&xs);
+        let data_loss = loss.loss(&ypred, &ys);
+
+        optim.zero_grad();
+        data_loss.backward();
+        optim.step();
+
+        let acc = accuracy.compute(&ypred, &ys);
+
+        println!(
+            "step {} - loss {:.3}, accuracy {:.2}%",
+            k,
+            data_loss.borrow().data,
+            acc * 100.0
+        );
+    }
+
+    let model_path = "model/seq_circles";
+    match model.save(model_path) {
+        Ok(_) => println!("\n> Model saved successfully at {model_path}"),
+        Err(err) => eprintln!("{}", err),
+    };
+
+    let loaded = build_model();
+    match loaded.load(model_path) {
+        Ok(_) => println!("> Model loaded successfully from {model_path}"),
+        Err(err) => eprintln!("{}", err),
+    };
+
+    let acc = accuracy.compute(&loaded.forward(&xs), &ys);
+    println!("Accuracy of loaded model {:.2}%", acc * 100.0);
+}
+
+fn build_model() -> Sequential {
+    Sequential::new()
+        .push(Layer::new(2, 16, None))
+        .push(Activation::new(ActvFn::ReLU))
+        .push(Layer::new(16, 16, None))
+        .push(LayerNorm::new(16, 1e-5))
+        .push(Activation::new(ActvFn::Tanh))
+        .push(Layer::new(16, 1, None))
+        .push(Activation::new(ActvFn::Sigmoid))
+}
@@ -1,11 +1,12 @@
 use ferrograd::{
     engine::{ActvFn, Value},
-    nn::{softmax, MultiLayerPerceptron},
+    nn::{softmax, Module, MultiLayerPerceptron},
+    utils::with_rng,
 };
 use rand::Rng;
 
 fn main() {
-    let model = MultiLayerPerceptron::new(784, vec![64, 32, 10], ActvFn::LeakyReLU);
+    let model = MultiLayerPerceptron::new(784, vec![64, 32, 10], ActvFn::LeakyReLU(0.01));
     println!("{}\n", model);
 
     let model_path = "model/mod_64x32";
@@ -15,7 +16,7 @@ fn main() {
     };
 
     let test_samples = 100;
-    let offset = rand::thread_rng().gen_range(0..9_900);
+    let offset = with_rng(|rng| rng.gen_range(0..9_900));
 
     let mnist = rust_mnist::Mnist::new("data/mnist/");
     let xtest: Vec<Vec<Value>> = mnist.test_data[offset..offset + test_samples]
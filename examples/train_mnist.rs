@@ -1,10 +1,10 @@
 use ferrograd::{
     engine::{ActvFn, Value},
-    loss::CrossEntropyLoss,
+    loss::{one_hot, ClassificationLoss, CrossEntropyWithLogitsLoss},
     metrics::BinaryAccuracy,
     nn::{
         optim::{l2_regularization, Adam},
-        softmax, MultiLayerPerceptron,
+        softmax, Init, Module, MultiLayerPerceptron,
     },
 };
 
@@ -12,11 +12,18 @@ fn main() {
     let batch_size = 100;
     let mnist = rust_mnist::Mnist::new("data/mnist/");
 
-    let model = MultiLayerPerceptron::new(784, vec![64, 32, 10], ActvFn::LeakyReLU);
+    let mut model = MultiLayerPerceptron::with_init(
+        784,
+        vec![64, 32, 10],
+        ActvFn::LeakyReLU(0.01),
+        &Init::HeNormal,
+        &Init::Zeros,
+    )
+    .with_dropout(0.2);
     println!("Model: {:#?}\n", model);
 
     let mut optim = Adam::new(model.parameters(), 0.1, 0.9, 0.999, 1e-8);
-    let loss = CrossEntropyLoss::new();
+    let loss = CrossEntropyWithLogitsLoss::new();
     let accuracy = BinaryAccuracy::new(0.5);
 
     println!(
@@ -32,12 +39,16 @@ fn main() {
         let end = (b + 1) * batch_size;
 
         let xtrain = images_to_features(&mnist.train_data[start..end]);
-        let ytrain = one_hot_encode(&mnist.train_labels[start..end]);
+        let labels: Vec<usize> = mnist.train_labels[start..end]
+            .iter()
+            .map(|digit| *digit as usize)
+            .collect();
+        let ytrain = one_hot(&labels, 10);
 
-        let ypred = model.forward(&xtrain);
-        let ypred = softmax(&ypred);
+        let logits = model.forward(&xtrain);
+        let ypred = softmax(&logits);
 
-        let data_loss = loss.loss(&ypred, &ytrain);
+        let data_loss = loss.loss_from_labels(&logits, &labels);
         let reg_loss = l2_regularization(0.0001, model.parameters());
         let total_loss = data_loss + reg_loss;
 
@@ -76,6 +87,7 @@ fn main() {
         .collect();
 
     println!("Testing");
+    model.eval();
     let ypred = softmax(&model.forward(&xtest));
 
     let correct = ypred
@@ -108,25 +120,3 @@ fn images_to_features(imgvec: &[[u8; 784]]) -> Vec<Vec<Value>> {
         })
         .collect()
 }
-
-fn one_hot_encode(labels: &[u8]) -> Vec<Vec<Value>> {
-    labels
-        .iter()
-        .map(|digit| {
-            let vec = match digit {
-                0 => vec![1, 0, 0, 0, 0, 0, 0, 0, 0, 0],
-                1 => vec![0, 1, 0, 0, 0, 0, 0, 0, 0, 0],
-                2 => vec![0, 0, 1, 0, 0, 0, 0, 0, 0, 0],
-                3 => vec![0, 0, 0, 1, 0, 0, 0, 0, 0, 0],
-                4 => vec![0, 0, 0, 0, 1, 0, 0, 0, 0, 0],
-                5 => vec![0, 0, 0, 0, 0, 1, 0, 0, 0, 0],
-                6 => vec![0, 0, 0, 0, 0, 0, 1, 0, 0, 0],
-                7 => vec![0, 0, 0, 0, 0, 0, 0, 1, 0, 0],
-                8 => vec![0, 0, 0, 0, 0, 0, 0, 0, 1, 0],
-                9 => vec![0, 0, 0, 0, 0, 0, 0, 0, 0, 1],
-                _ => panic!("Invalid digit"),
-            };
-            Value::from_1d(&vec)
-        })
-        .collect()
-}
@@ -0,0 +1,118 @@
+use ferrograd::{
+    engine::Value,
+    loss::{ClassificationLoss, CrossEntropyLoss},
+    nn::{optim::Adam, softmax, Embedding, Layer, LayerNorm, Module, TransformerBlock},
+    utils::{manual_seed, with_rng},
+};
+use rand::Rng;
+
+const TEXT: &str = "the cat sat on the mat. the dog sat on the log. the cat and the dog ran.";
+const BLOCK_SIZE: usize = 8;
+const EMBED_DIM: u32 = 16;
+
+// Character-level language model with a single causal transformer block.
+struct Model {
+    tokens: Embedding,
+    positions: Embedding,
+    block: TransformerBlock,
+    ln: LayerNorm,
+    head: Layer,
+}
+
+impl Model {
+    fn new(vocab_size: u32) -> Model {
+        Model {
+            tokens: Embedding::new(vocab_size, EMBED_DIM),
+            positions: Embedding::new(BLOCK_SIZE as u32, EMBED_DIM),
+            block: TransformerBlock::new(EMBED_DIM, 2, 4 * EMBED_DIM, true),
+            ln: LayerNorm::new(EMBED_DIM, 1e-5),
+            head: Layer::new(EMBED_DIM, vocab_size, None),
+        }
+    }
+
+    // Probabilities of the next character, at each position of the context.
+    fn forward(&self, context: &[usize]) -> Vec<Vec<Value>> {
+        let positions: Vec<usize> = (0..context.len()).collect();
+        let x: Vec<Vec<Value>> = self
+            .tokens
+            .lookup(context)
+            .iter()
+            .zip(self.positions.lookup(&positions))
+            .map(|(tok, pos)| tok.iter().zip(pos).map(|(t, p)| t + p).collect())
+            .collect();
+
+        let x = self.block.forward_sequence(&x);
+        softmax(&self.head.forward(&self.ln.forward(&x)))
+    }
+
+    fn parameters(&self) -> Vec<Value> {
+        let modules: [&dyn Module; 5] = [
+            &self.tokens,
+            &self.positions,
+            &self.block,
+            &self.ln,
+            &self.head,
+        ];
+        modules.iter().flat_map(|m| m.parameters()).collect()
+    }
+}
+
+fn main() {
+    manual_seed(42);
+
+    let mut vocab: Vec<char> = TEXT.chars().collect();
+    vocab.sort();
+    vocab.dedup();
+    let index = |c: char| vocab.iter().position(|&v| v == c).expect("Unknown char");
+    let chars: Vec<usize> = TEXT.chars().map(index).collect();
+
+    let model = Model::new(vocab.len() as u32);
+    println!("{}\n{}\n{}\n", model.tokens, model.block, model.head);
+
+    let mut optim = Adam::new(model.parameters(), 0.01, 0.9, 0.999, 1e-8);
+    let loss = CrossEntropyLoss::new();
+
+    for k in 0..100 {
+        // Batch of random windows, with the targets shifted by one character.
+        let starts: Vec<usize> = with_rng(|rng| {
+            (0..4)
+                .map(|_| rng.gen_range(0..chars.len() - BLOCK_SIZE))
+                .collect()
+        });
+
+        let (mut ypred, mut labels) = (vec![], vec![]);
+        for start in starts {
+            ypred.extend(model.forward(&chars[start..start + BLOCK_SIZE]));
+            labels.extend(&chars[start + 1..start + BLOCK_SIZE + 1]);
+        }
+        let data_loss = loss.loss_from_labels(&ypred, &labels);
+
+        optim.zero_grad();
+        data_loss.backward();
+        optim.step();
+
+        println!("step {} - loss {:.4}", k, data_loss.borrow().data);
+    }
+
+    let mut sample = vec![index('t')];
+    for _ in 0..60 {
+        let context = &sample[sample.len().saturating_sub(BLOCK_SIZE)..];
+        let probs = model.forward(context);
+        sample.push(sample_index(&probs[probs.len() - 1]));
+    }
+    let sample: String = sample.iter().map(|&i| vocab[i]).collect();
+    println!("\nSample: {}", sample);
+}
+
+fn sample_index(probs: &[Value]) -> usize {
+    let r: f64 = with_rng(|rng| rng.gen());
+    let mut cumulative = 0.0;
+
+    for (i, p) in probs.iter().enumerate() {
+        cumulative += p.borrow().data;
+        if r < cumulative {
+            return i;
+        }
+    }
+    probs.len() - 1
+}
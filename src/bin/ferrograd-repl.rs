@@ -0,0 +1,114 @@
+use ferrograd::engine::{parse, Value};
+use std::{
+    collections::HashMap,
+    io::{self, BufRead, Write},
+};
+
+const HELP: &str = "\
+Declare variables with `a = 2.5` and type expressions to evaluate them. Variable names are a
+single letter, which is how expression trees print them.
+Expressions support + - * / ^, ln, exp, and the activation functions relu, leaky_relu,
+tanh, sigmoid, gelu, gelu_tanh, silu, elu, selu, softplus, softsign, mish, hard_sigmoid
+and hard_tanh. `LeakyReLU(0.2)(x)` sets the slope of leaky_relu, which is 0.01 by default.
+
+Commands -
+grad  Re-evaluate the last expression with the current variables, print its value and the
+      gradients of the variables
+tree  Print the expression tree of the last expression
+vars  Print the declared variables
+help  Print this message
+quit  Exit the REPL";
+
+fn main() {
+    println!("ferrograd REPL, type `help` for help.");
+
+    let mut vars: HashMap<String, Value> = HashMap::new();
+    let mut last: Option<(String, Value)> = None;
+
+    let stdin = io::stdin();
+    let mut lines = stdin.lock().lines();
+
+    loop {
+        print!("> ");
+        io::stdout().flush().expect("Error in flushing stdout");
+
+        let line = match lines.next() {
+            Some(Ok(line)) => line,
+            _ => break,
+        };
+
+        match line.trim() {
+            "" => {}
+            "quit" | "exit" => break,
+            "help" => println!("{}", HELP),
+            "vars" => print_vars(&vars, |v| v.borrow().data, ""),
+            "tree" => match &last {
+                Some((_, value)) => print!("{}", value.tree()),
+                None => eprintln!("No expression to print"),
+            },
+            "grad" => match &last {
+                Some((src, _)) => {
+                    // Rebuild the graph so that repeated calls do not accumulate gradients.
+                    for var in vars.values() {
+                        var.borrow_mut().grad = 0.0;
+                    }
+                    let value = parse(src, &vars).expect("Error in parsing last expression");
+                    value.backward();
+
+                    println!("{:.4}", value.borrow().data);
+                    print_vars(&vars, |v| v.borrow().grad, ".grad");
+                    last = Some((src.clone(), value));
+                }
+                None => eprintln!("No expression to differentiate"),
+            },
+            line => match line.split_once('=') {
+                Some((name, src)) => declare(&mut vars, name.trim(), src.trim()),
+                None => match parse(line, &vars) {
+                    Ok(value) => {
+                        println!("{:.4}", value.borrow().data);
+                        last = Some((line.to_string(), value));
+                    }
+                    Err(err) => print_error(line, &err.to_string(), err.position),
+                },
+            },
+        }
+    }
+}
+
+fn declare(vars: &mut HashMap<String, Value>, name: &str, src: &str) {
+    let mut chars = name.chars();
+    let letter = match (chars.next(), chars.next()) {
+        (Some(c), None) if c.is_alphabetic() => c,
+        _ => {
+            eprintln!("Invalid variable name `{}`, expected a single letter", name);
+            return;
+        }
+    };
+
+    match parse(src, vars) {
+        Ok(value) => {
+            let data = value.borrow().data;
+            println!("{} = {:.4}", name, data);
+            vars.insert(name.to_string(), Value::new(data).with_name(letter));
+        }
+        Err(err) => print_error(src, &err.to_string(), err.position),
+    }
+}
+
+fn print_vars<F>(vars: &HashMap<String, Value>, field: F, suffix: &str)
+where
+    F: Fn(&Value) -> f64,
+{
+    let mut names: Vec<&String> = vars.keys().collect();
+    names.sort();
+
+    for name in names {
+        println!("{}{} = {:.4}", name, suffix, field(&vars[name]));
+    }
+}
+
+fn print_error(src: &str, message: &str, position: usize) {
+    eprintln!("  {}", src);
+    eprintln!("  {}^", " ".repeat(position));
+    eprintln!("Error: {}", message);
+}
@@ -1,12 +1,46 @@
 use crate::engine::value::{ActvFn, Op, Prev, Value, V};
+use std::f64::consts::{FRAC_2_SQRT_PI, SQRT_2};
+
+// Constants of SELU, from Klambauer et al.
+const SELU_LAMBDA: f64 = 1.050_700_987_355_480_5;
+const SELU_ALPHA: f64 = 1.673_263_242_354_377_2;
+
+// √(2 / π) and the cubic coefficient of the tanh approximation of GELU.
+const GELU_SQRT_2_PI: f64 = FRAC_2_SQRT_PI / SQRT_2;
+const GELU_COEFF: f64 = 0.044_715;
 
 impl Value {
+    /// Apply the given activation function. Panics on `PReLU`, use `prelu` with a slope Value.
+    pub fn activate(&self, actv_fn: ActvFn) -> Value {
+        assert!(
+            !matches!(actv_fn, ActvFn::PReLU(_)),
+            "PReLU needs a learnable slope, use prelu or a Neuron"
+        );
+        match actv_fn {
+            ActvFn::ReLU => self.relu(),
+            ActvFn::Tanh => self.tanh(),
+            ActvFn::Sigmoid => self.sigmoid(),
+            actv_fn => Value::init(
+                actv_fn.apply(self.borrow().data),
+                Some(|value: &V| {
+                    if let (Prev::Unary(a), Op::ActvFn(actv_fn)) = (&value.prev, &value.op) {
+                        let x = a.borrow().data;
+                        a.accumulate_grad(actv_fn.derivative(x) * value.grad);
+                    }
+                }),
+                Prev::Unary(self.clone()),
+                Op::ActvFn(actv_fn),
+                None,
+            ),
+        }
+    }
+
     pub fn relu(&self) -> Value {
         Value::init(
             self.borrow().data.max(0.0),
             Some(|value: &V| {
                 if let Prev::Unary(a) = &value.prev {
-                    a.borrow_mut().grad += if value.data > 0.0 { value.grad } else { 0.0 };
+                    a.accumulate_grad(if value.data > 0.0 { value.grad } else { 0.0 });
                 }
             }),
             Prev::Unary(self.clone()),
@@ -15,23 +49,9 @@ impl Value {
         )
     }
 
+    /// LeakyReLU with a slope of 0.01.
     pub fn leaky_relu(&self) -> Value {
-        let x = self.borrow().data;
-        Value::init(
-            x.max(0.01 * x),
-            Some(|value: &V| {
-                if let Prev::Unary(a) = &value.prev {
-                    a.borrow_mut().grad += if value.data > 0.0 {
-                        value.grad
-                    } else {
-                        0.01 * value.grad
-                    };
-                }
-            }),
-            Prev::Unary(self.clone()),
-            Op::ActvFn(ActvFn::LeakyReLU),
-            None,
-        )
+        self.activate(ActvFn::LeakyReLU(0.01))
     }
 
     pub fn tanh(&self) -> Value {
@@ -40,7 +60,7 @@ impl Value {
             (e2x - 1.0) / (e2x + 1.0),
             Some(|value: &V| {
                 if let Prev::Unary(a) = &value.prev {
-                    a.borrow_mut().grad += (1.0 - value.data.powi(2)) * value.grad;
+                    a.accumulate_grad((1.0 - value.data.powi(2)) * value.grad);
                 }
             }),
             Prev::Unary(self.clone()),
@@ -50,12 +70,12 @@ impl Value {
     }
 
     pub fn sigmoid(&self) -> Value {
-        let em1x = (-1.0 * self.borrow().data).exp();
+        let em1x = (-self.borrow().data).exp();
         Value::init(
             1.0 / (1.0 + em1x),
             Some(|value: &V| {
                 if let Prev::Unary(a) = &value.prev {
-                    a.borrow_mut().grad += value.data * (1.0 - value.data) * value.grad;
+                    a.accumulate_grad(value.data * (1.0 - value.data) * value.grad);
                 }
             }),
             Prev::Unary(self.clone()),
@@ -63,4 +83,221 @@ impl Value {
             None,
         )
     }
+
+    pub fn gelu(&self) -> Value {
+        self.activate(ActvFn::GELU)
+    }
+
+    pub fn gelu_tanh(&self) -> Value {
+        self.activate(ActvFn::GELUTanh)
+    }
+
+    pub fn silu(&self) -> Value {
+        self.activate(ActvFn::SiLU)
+    }
+
+    pub fn elu(&self) -> Value {
+        self.activate(ActvFn::ELU)
+    }
+
+    pub fn selu(&self) -> Value {
+        self.activate(ActvFn::SELU)
+    }
+
+    pub fn softplus(&self) -> Value {
+        self.activate(ActvFn::Softplus)
+    }
+
+    pub fn softsign(&self) -> Value {
+        self.activate(ActvFn::Softsign)
+    }
+
+    pub fn mish(&self) -> Value {
+        self.activate(ActvFn::Mish)
+    }
+
+    pub fn hard_sigmoid(&self) -> Value {
+        self.activate(ActvFn::HardSigmoid)
+    }
+
+    pub fn hard_tanh(&self) -> Value {
+        self.activate(ActvFn::HardTanh)
+    }
+
+    /// PReLU with a learnable slope for the negative part.
+    pub fn prelu(&self, slope: &Value) -> Value {
+        let (x, a) = (self.borrow().data, slope.borrow().data);
+        Value::init(
+            if x > 0.0 { x } else { a * x },
+            Some(|value: &V| {
+                if let Prev::Binary(x, a) = &value.prev {
+                    let (x_data, a_data) = (x.borrow().data, a.borrow().data);
+                    if x_data > 0.0 {
+                        x.accumulate_grad(value.grad);
+                    } else {
+                        x.accumulate_grad(a_data * value.grad);
+                        a.accumulate_grad(x_data * value.grad);
+                    }
+                }
+            }),
+            Prev::Binary(self.clone(), slope.clone()),
+            Op::ActvFn(ActvFn::PReLU(a)),
+            None,
+        )
+    }
+}
+
+impl ActvFn {
+    /// Value of the activation function at x.
+    pub fn apply(&self, x: f64) -> f64 {
+        match self {
+            ActvFn::ReLU => x.max(0.0),
+            ActvFn::LeakyReLU(slope) | ActvFn::PReLU(slope) => {
+                if x > 0.0 {
+                    x
+                } else {
+                    slope * x
+                }
+            }
+            ActvFn::Tanh => x.tanh(),
+            ActvFn::Sigmoid => sigmoid(x),
+            ActvFn::GELU => x * normal_cdf(x),
+            ActvFn::GELUTanh => {
+                0.5 * x * (1.0 + (GELU_SQRT_2_PI * (x + GELU_COEFF * x.powi(3))).tanh())
+            }
+            ActvFn::SiLU => x * sigmoid(x),
+            ActvFn::ELU => {
+                if x > 0.0 {
+                    x
+                } else {
+                    x.exp_m1()
+                }
+            }
+            ActvFn::SELU => {
+                if x > 0.0 {
+                    SELU_LAMBDA * x
+                } else {
+                    SELU_LAMBDA * SELU_ALPHA * x.exp_m1()
+                }
+            }
+            ActvFn::Softplus => softplus(x),
+            ActvFn::Softsign => x / (1.0 + x.abs()),
+            ActvFn::Mish => x * softplus(x).tanh(),
+            ActvFn::HardSigmoid => (x / 6.0 + 0.5).clamp(0.0, 1.0),
+            ActvFn::HardTanh => x.clamp(-1.0, 1.0),
+            ActvFn::Custom { forward, .. } => forward(x),
+        }
+    }
+
+    /// Derivative of the activation function at x.
+    pub fn derivative(&self, x: f64) -> f64 {
+        match self {
+            ActvFn::ReLU => step(x),
+            ActvFn::LeakyReLU(slope) | ActvFn::PReLU(slope) => {
+                if x > 0.0 {
+                    1.0
+                } else {
+                    *slope
+                }
+            }
+            ActvFn::Tanh => 1.0 - x.tanh().powi(2),
+            ActvFn::Sigmoid => sigmoid(x) * (1.0 - sigmoid(x)),
+            ActvFn::GELU => {
+                let pdf = (-0.5 * x * x).exp() * FRAC_2_SQRT_PI / (2.0 * SQRT_2);
+                normal_cdf(x) + x * pdf
+            }
+            ActvFn::GELUTanh => {
+                let inner = GELU_SQRT_2_PI * (x + GELU_COEFF * x.powi(3));
+                let d_inner = GELU_SQRT_2_PI * (1.0 + 3.0 * GELU_COEFF * x * x);
+                let t = inner.tanh();
+                0.5 * (1.0 + t) + 0.5 * x * (1.0 - t * t) * d_inner
+            }
+            ActvFn::SiLU => {
+                let s = sigmoid(x);
+                s * (1.0 + x * (1.0 - s))
+            }
+            ActvFn::ELU => {
+                if x > 0.0 {
+                    1.0
+                } else {
+                    x.exp()
+                }
+            }
+            ActvFn::SELU => {
+                if x > 0.0 {
+                    SELU_LAMBDA
+                } else {
+                    SELU_LAMBDA * SELU_ALPHA * x.exp()
+                }
+            }
+            ActvFn::Softplus => sigmoid(x),
+            ActvFn::Softsign => 1.0 / (1.0 + x.abs()).powi(2),
+            ActvFn::Mish => {
+                let t = softplus(x).tanh();
+                t + x * (1.0 - t * t) * sigmoid(x)
+            }
+            ActvFn::HardSigmoid => {
+                if -3.0 < x && x < 3.0 {
+                    1.0 / 6.0
+                } else {
+                    0.0
+                }
+            }
+            ActvFn::HardTanh => {
+                if -1.0 < x && x < 1.0 {
+                    1.0
+                } else {
+                    0.0
+                }
+            }
+            ActvFn::Custom { derivative, .. } => derivative(x),
+        }
+    }
+}
+
+fn step(x: f64) -> f64 {
+    if x > 0.0 {
+        1.0
+    } else {
+        0.0
+    }
+}
+
+fn sigmoid(x: f64) -> f64 {
+    1.0 / (1.0 + (-x).exp())
+}
+
+// ln(1 + eˣ), without overflow for large x.
+fn softplus(x: f64) -> f64 {
+    x.max(0.0) + (-x.abs()).exp().ln_1p()
+}
+
+// Φ(x), the CDF of the standard normal distribution.
+fn normal_cdf(x: f64) -> f64 {
+    0.5 * (1.0 + erf(x / SQRT_2))
+}
+
+// Error function, from its Taylor series near 0 and the continued fraction of erfc elsewhere.
+fn erf(x: f64) -> f64 {
+    if x < 0.0 {
+        return -erf(-x);
+    }
+
+    if x < 2.5 {
+        let (mut term, mut sum) = (x, x);
+        let mut n = 0.0;
+        while term.abs() > 1e-17 * sum.abs() {
+            n += 1.0;
+            term *= -x * x / n;
+            sum += term / (2.0 * n + 1.0);
+        }
+        sum * FRAC_2_SQRT_PI
+    } else {
+        // erfc(x) = e^(-x²) / √π · 1 / (x + (1/2) / (x + 1 / (x + (3/2) / (x + ...))))
+        let mut fraction = x;
+        for k in (1..60).rev() {
+            fraction = x + (k as f64 / 2.0) / fraction;
+        }
+        1.0 - (-x * x).exp() * FRAC_2_SQRT_PI / 2.0 / fraction
+    }
 }
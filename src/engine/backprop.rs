@@ -22,6 +22,14 @@ impl Value {
         }
     }
 
+    // Used by the backward functions of the ops, skipping frozen Values.
+    pub(crate) fn accumulate_grad(&self, grad: f64) {
+        let mut value = self.borrow_mut();
+        if !value.frozen {
+            value.grad += grad;
+        }
+    }
+
     fn topological_sort(&self, topo: &mut Vec<Value>, visited: &mut HashSet<Value>) {
         if visited.insert(self.clone()) {
             match &self.borrow().prev {
@@ -32,6 +40,11 @@ impl Value {
                 Prev::Unary(a) => {
                     a.topological_sort(topo, visited);
                 }
+                Prev::Many(operands) => {
+                    for a in operands {
+                        a.topological_sort(topo, visited);
+                    }
+                }
                 Prev::Init => {}
             };
             topo.push(self.clone());
@@ -50,6 +63,11 @@ impl Value {
             Prev::Unary(a) => {
                 root.push(a.tree());
             }
+            Prev::Many(operands) => {
+                for a in operands {
+                    root.push(a.tree());
+                }
+            }
             Prev::Init => {}
         }
 
@@ -0,0 +1,369 @@
+use crate::engine::value::{ActvFn, Op, Prev, Value};
+use std::fmt;
+
+/**
+Symbolic form of the expression a `Value` represents.
+
+- Leaves named with `with_name` are rendered as variables, unnamed leaves as their data.
+- Subtraction, negation and division, which the engine builds out of `+`, `*` and `^`,
+  are recovered so that the output reads like the code that built the graph.
+*/
+#[derive(Debug, Clone)]
+pub enum Expr {
+    Const(f64),
+    Var(char),
+    Add(Box<Expr>, Box<Expr>),
+    Sub(Box<Expr>, Box<Expr>),
+    Mul(Box<Expr>, Box<Expr>),
+    Div(Box<Expr>, Box<Expr>),
+    Neg(Box<Expr>),
+    Pow(Box<Expr>, f64),
+    Ln(Box<Expr>),
+    Exp(Box<Expr>),
+    ActvFn(ActvFn, Box<Expr>),
+    /// Derivative of the given order of an activation function, that has no closed form in terms
+    /// of the other ops.
+    Deriv(ActvFn, u32, Box<Expr>),
+}
+
+impl Value {
+    /// Symbolic expression of the computation graph rooted at this `Value`.
+    pub fn expr(&self) -> Expr {
+        let v = self.borrow();
+
+        match (&v.op, &v.prev) {
+            (Op::Var, _) => match v.name {
+                Some(name) => Expr::Var(name),
+                None => Expr::Const(v.data),
+            },
+            (Op::Add, Prev::Binary(a, b)) => match (a.expr(), b.expr()) {
+                (a, Expr::Neg(b)) => Expr::Sub(Box::new(a), b),
+                (a, Expr::Const(c)) if c < 0.0 => Expr::Sub(Box::new(a), Box::new(Expr::Const(-c))),
+                (a, b) => Expr::Add(Box::new(a), Box::new(b)),
+            },
+            (Op::Mul, Prev::Binary(a, b)) => match (a.expr(), b.expr()) {
+                (a, Expr::Const(-1.0)) => Expr::Neg(Box::new(a)),
+                (Expr::Const(-1.0), b) => Expr::Neg(Box::new(b)),
+                (a, Expr::Div(one, b)) if matches!(*one, Expr::Const(1.0)) => {
+                    Expr::Div(Box::new(a), b)
+                }
+                (Expr::Div(one, a), b) if matches!(*one, Expr::Const(1.0)) => {
+                    Expr::Div(Box::new(b), a)
+                }
+                (a, Expr::Const(c)) if is_reciprocal(c) => {
+                    Expr::Div(Box::new(a), Box::new(Expr::Const(1.0 / c)))
+                }
+                (a, b) => Expr::Mul(Box::new(a), Box::new(b)),
+            },
+            (Op::Pow, Prev::Binary(a, b)) => {
+                let power = b.borrow().data;
+                if power == -1.0 {
+                    Expr::Div(Box::new(Expr::Const(1.0)), Box::new(a.expr()))
+                } else {
+                    Expr::Pow(Box::new(a.expr()), power)
+                }
+            }
+            (Op::Ln, Prev::Unary(a)) => Expr::Ln(Box::new(a.expr())),
+            (Op::Exp, Prev::Unary(a)) => Expr::Exp(Box::new(a.expr())),
+            (Op::ActvFn(actv_fn), Prev::Unary(a)) => Expr::ActvFn(*actv_fn, Box::new(a.expr())),
+            // PReLU with a learnable slope, as ReLU(x) - slope * ReLU(-x).
+            (Op::ActvFn(_), Prev::Binary(x, slope)) => {
+                let relu = |x: Expr| Expr::ActvFn(ActvFn::ReLU, Box::new(x));
+                Expr::Sub(
+                    Box::new(relu(x.expr())),
+                    Box::new(Expr::Mul(
+                        Box::new(slope.expr()),
+                        Box::new(relu(Expr::Neg(Box::new(x.expr())))),
+                    )),
+                )
+            }
+            // Σ tᵢ (ln(Σ exp(xⱼ)) - xᵢ), with the logits followed by the targets.
+            (Op::CrossEntropy, Prev::Many(operands)) => {
+                let (logits, target) = operands.split_at(operands.len() / 2);
+                let lse = Expr::Ln(Box::new(
+                    logits
+                        .iter()
+                        .map(|x| Expr::Exp(Box::new(x.expr())))
+                        .reduce(add)
+                        .unwrap_or(Expr::Const(0.0)),
+                ));
+
+                logits
+                    .iter()
+                    .zip(target)
+                    .map(|(x, t)| mul(t.expr(), sub(lse.clone(), x.expr())))
+                    .reduce(add)
+                    .unwrap_or(Expr::Const(0.0))
+            }
+            _ => Expr::Const(v.data),
+        }
+    }
+}
+
+// Division by an f64 is stored as multiplication by its reciprocal, `x / 50.0` becomes `x * 0.02`.
+fn is_reciprocal(c: f64) -> bool {
+    let r = 1.0 / c;
+    c != 0.0 && r.abs() > 1.0 && r.fract() == 0.0 && 1.0 / r == c
+}
+
+impl Expr {
+    /// Symbolic derivative of the expression with respect to the variable named `wrt`.
+    pub fn derivative(&self, wrt: char) -> Expr {
+        match self {
+            Expr::Const(_) => Expr::Const(0.0),
+            Expr::Var(name) => Expr::Const(if *name == wrt { 1.0 } else { 0.0 }),
+            Expr::Add(a, b) => add(a.derivative(wrt), b.derivative(wrt)),
+            Expr::Sub(a, b) => sub(a.derivative(wrt), b.derivative(wrt)),
+            Expr::Mul(a, b) => add(
+                mul(a.derivative(wrt), *b.clone()),
+                mul(*a.clone(), b.derivative(wrt)),
+            ),
+            Expr::Div(a, b) => match b.derivative(wrt) {
+                Expr::Const(0.0) => div(a.derivative(wrt), *b.clone()),
+                db => div(
+                    sub(mul(a.derivative(wrt), *b.clone()), mul(*a.clone(), db)),
+                    pow(*b.clone(), 2.0),
+                ),
+            },
+            Expr::Neg(a) => neg(a.derivative(wrt)),
+            Expr::Pow(a, p) => mul(
+                mul(Expr::Const(*p), pow(*a.clone(), p - 1.0)),
+                a.derivative(wrt),
+            ),
+            Expr::Ln(a) => div(a.derivative(wrt), *a.clone()),
+            Expr::Exp(a) => mul(self.clone(), a.derivative(wrt)),
+            Expr::ActvFn(actv_fn, a) => {
+                let sigmoid = || Expr::ActvFn(ActvFn::Sigmoid, a.clone());
+                let outer = match actv_fn {
+                    ActvFn::Tanh => sub(Expr::Const(1.0), pow(self.clone(), 2.0)),
+                    ActvFn::Sigmoid => mul(self.clone(), sub(Expr::Const(1.0), self.clone())),
+                    ActvFn::Softplus => sigmoid(),
+                    ActvFn::SiLU => mul(
+                        sigmoid(),
+                        add(
+                            Expr::Const(1.0),
+                            mul(*a.clone(), sub(Expr::Const(1.0), sigmoid())),
+                        ),
+                    ),
+                    _ => Expr::Deriv(*actv_fn, 1, a.clone()),
+                };
+                mul(outer, a.derivative(wrt))
+            }
+            Expr::Deriv(actv_fn, order, a) => match actv_fn {
+                // Piecewise linear
+                ActvFn::ReLU
+                | ActvFn::LeakyReLU(_)
+                | ActvFn::PReLU(_)
+                | ActvFn::HardSigmoid
+                | ActvFn::HardTanh => Expr::Const(0.0),
+                _ => mul(
+                    Expr::Deriv(*actv_fn, order + 1, a.clone()),
+                    a.derivative(wrt),
+                ),
+            },
+        }
+    }
+
+    /// Render the expression as LaTeX.
+    pub fn to_latex(&self) -> String {
+        let mut s = String::new();
+        self.write(&mut s, 0, Style::Latex)
+            .expect("Error in writing to String");
+        s
+    }
+
+    // Binding strength of the outermost operator.
+    fn precedence(&self, style: Style) -> u8 {
+        match self {
+            Expr::Add(_, _) | Expr::Sub(_, _) => 1,
+            Expr::Div(_, _) if style == Style::Latex => 5,
+            Expr::Mul(_, _) | Expr::Div(_, _) => 2,
+            Expr::Neg(_) => 3,
+            Expr::Const(c) if *c < 0.0 => 3,
+            Expr::Pow(_, _) => 4,
+            Expr::Exp(_) if style == Style::Latex => 4,
+            _ => 5,
+        }
+    }
+
+    fn write<W: fmt::Write>(&self, f: &mut W, min_prec: u8, style: Style) -> fmt::Result {
+        let parens = self.precedence(style) < min_prec;
+        let (open, close) = match (parens, style) {
+            (false, _) => ("", ""),
+            (true, Style::Infix) => ("(", ")"),
+            (true, Style::Latex) => ("\\left(", "\\right)"),
+        };
+        write!(f, "{}", open)?;
+
+        match (self, style) {
+            (Expr::Const(c), _) => write!(f, "{}", c)?,
+            (Expr::Var(name), _) => write!(f, "{}", name)?,
+            (Expr::Add(a, b), _) => {
+                a.write(f, 1, style)?;
+                write!(f, " + ")?;
+                b.write(f, 1, style)?;
+            }
+            (Expr::Sub(a, b), _) => {
+                a.write(f, 1, style)?;
+                write!(f, " - ")?;
+                b.write(f, 2, style)?;
+            }
+            (Expr::Mul(a, b), Style::Infix) => {
+                a.write(f, 2, style)?;
+                write!(f, " * ")?;
+                b.write(f, 2, style)?;
+            }
+            (Expr::Mul(a, b), Style::Latex) => {
+                a.write(f, 2, style)?;
+                write!(f, " \\cdot ")?;
+                b.write(f, 2, style)?;
+            }
+            (Expr::Div(a, b), Style::Infix) => {
+                a.write(f, 2, style)?;
+                write!(f, " / ")?;
+                b.write(f, 3, style)?;
+            }
+            (Expr::Div(a, b), Style::Latex) => {
+                write!(f, "\\frac{{")?;
+                a.write(f, 0, style)?;
+                write!(f, "}}{{")?;
+                b.write(f, 0, style)?;
+                write!(f, "}}")?;
+            }
+            (Expr::Neg(a), _) => {
+                write!(f, "-")?;
+                a.write(f, 3, style)?;
+            }
+            (Expr::Pow(a, p), Style::Infix) => {
+                a.write(f, 5, style)?;
+                if *p < 0.0 {
+                    write!(f, "^({})", p)?;
+                } else {
+                    write!(f, "^{}", p)?;
+                }
+            }
+            (Expr::Pow(a, p), Style::Latex) => {
+                a.write(f, 5, style)?;
+                write!(f, "^{{{}}}", p)?;
+            }
+            (Expr::Exp(a), Style::Latex) => {
+                write!(f, "e^{{")?;
+                a.write(f, 0, style)?;
+                write!(f, "}}")?;
+            }
+            (Expr::Ln(a), _) => write_call(f, &Op::Ln.to_string(), a, style)?,
+            (Expr::Exp(a), Style::Infix) => write_call(f, &Op::Exp.to_string(), a, style)?,
+            (Expr::ActvFn(actv_fn, a), _) => write_call(f, &fn_name(*actv_fn, style), a, style)?,
+            (Expr::Deriv(actv_fn, order, a), _) => {
+                let primes = "'".repeat(*order as usize);
+                write_call(
+                    f,
+                    &format!("{}{}", fn_name(*actv_fn, style), primes),
+                    a,
+                    style,
+                )?
+            }
+        }
+
+        write!(f, "{}", close)
+    }
+}
+
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write(f, 0, Style::Infix)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Style {
+    Infix,
+    Latex,
+}
+
+fn fn_name(actv_fn: ActvFn, style: Style) -> String {
+    match (actv_fn, style) {
+        (_, Style::Infix) => Op::ActvFn(actv_fn).to_string(),
+        (ActvFn::Tanh, Style::Latex) => "\\tanh".to_string(),
+        (ActvFn::Sigmoid, Style::Latex) => "\\sigma".to_string(),
+        (ActvFn::LeakyReLU(slope), Style::Latex) => {
+            format!("\\operatorname{{LeakyReLU}}_{{{}}}", slope)
+        }
+        (_, Style::Latex) => format!("\\operatorname{{{}}}", Op::ActvFn(actv_fn)),
+    }
+}
+
+fn write_call<W: fmt::Write>(f: &mut W, name: &str, arg: &Expr, style: Style) -> fmt::Result {
+    match style {
+        Style::Infix => {
+            write!(f, "{}(", name)?;
+            arg.write(f, 0, style)?;
+            write!(f, ")")
+        }
+        Style::Latex => {
+            let name = if name == "ln" { "\\ln" } else { name };
+            write!(f, "{}\\left(", name)?;
+            arg.write(f, 0, style)?;
+            write!(f, "\\right)")
+        }
+    }
+}
+
+// Constructors that fold constants, used to keep derivatives readable.
+
+fn add(a: Expr, b: Expr) -> Expr {
+    match (a, b) {
+        (Expr::Const(x), Expr::Const(y)) => Expr::Const(x + y),
+        (Expr::Const(0.0), b) => b,
+        (a, Expr::Const(0.0)) => a,
+        (a, Expr::Neg(b)) => sub(a, *b),
+        (a, b) => Expr::Add(Box::new(a), Box::new(b)),
+    }
+}
+
+fn sub(a: Expr, b: Expr) -> Expr {
+    match (a, b) {
+        (Expr::Const(x), Expr::Const(y)) => Expr::Const(x - y),
+        (Expr::Const(0.0), b) => neg(b),
+        (a, Expr::Const(0.0)) => a,
+        (a, b) => Expr::Sub(Box::new(a), Box::new(b)),
+    }
+}
+
+fn mul(a: Expr, b: Expr) -> Expr {
+    match (a, b) {
+        (Expr::Const(x), Expr::Const(y)) => Expr::Const(x * y),
+        (Expr::Const(0.0), _) | (_, Expr::Const(0.0)) => Expr::Const(0.0),
+        (Expr::Const(1.0), b) => b,
+        (a, Expr::Const(1.0)) => a,
+        (Expr::Const(-1.0), b) => neg(b),
+        (a, Expr::Const(-1.0)) => neg(a),
+        (a, b) => Expr::Mul(Box::new(a), Box::new(b)),
+    }
+}
+
+fn div(a: Expr, b: Expr) -> Expr {
+    match (a, b) {
+        (Expr::Const(x), Expr::Const(y)) => Expr::Const(x / y),
+        (Expr::Const(0.0), _) => Expr::Const(0.0),
+        (a, Expr::Const(1.0)) => a,
+        (a, b) => Expr::Div(Box::new(a), Box::new(b)),
+    }
+}
+
+fn neg(a: Expr) -> Expr {
+    match a {
+        Expr::Const(x) => Expr::Const(-x),
+        Expr::Neg(a) => *a,
+        a => Expr::Neg(Box::new(a)),
+    }
+}
+
+fn pow(a: Expr, p: f64) -> Expr {
+    match a {
+        _ if p == 0.0 => Expr::Const(1.0),
+        a if p == 1.0 => a,
+        Expr::Const(x) => Expr::Const(x.powf(p)),
+        a => Expr::Pow(Box::new(a), p),
+    }
+}
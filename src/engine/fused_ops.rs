@@ -0,0 +1,48 @@
+use crate::engine::value::{Op, Prev, Value, V};
+
+impl Value {
+    /**
+    Cross-entropy of a row of logits against target probabilities, `-Σ tᵢ log_softmax(x)ᵢ`, as a
+    single op. The log-sum-exp is shifted by the largest logit, so large logits do not overflow,
+    and the gradient of the logits is `softmax(x) Σ tⱼ - t`, which is `softmax(x) - t` for
+    targets that sum to 1. Targets receive no gradient.
+    */
+    pub fn cross_entropy_with_logits(logits: &[Value], target: &[Value]) -> Value {
+        assert!(
+            logits.len() == target.len(),
+            "Mismatching lengths of logits and target"
+        );
+
+        let x: Vec<f64> = logits.iter().map(|x_i| x_i.borrow().data).collect();
+        let t: Vec<f64> = target.iter().map(|t_i| t_i.borrow().data).collect();
+        let lse = log_sum_exp(&x);
+        let loss = x.iter().zip(&t).map(|(x_i, t_i)| t_i * (lse - x_i)).sum();
+
+        let operands = logits.iter().chain(target).cloned().collect();
+        Value::init(
+            loss,
+            Some(|value: &V| {
+                if let Prev::Many(operands) = &value.prev {
+                    let (logits, target) = operands.split_at(operands.len() / 2);
+                    let x: Vec<f64> = logits.iter().map(|x_i| x_i.borrow().data).collect();
+                    let t: Vec<f64> = target.iter().map(|t_i| t_i.borrow().data).collect();
+                    let (lse, t_sum) = (log_sum_exp(&x), t.iter().sum::<f64>());
+
+                    for ((logit, x_i), t_i) in logits.iter().zip(x).zip(t) {
+                        let softmax_i = (x_i - lse).exp();
+                        logit.accumulate_grad((softmax_i * t_sum - t_i) * value.grad);
+                    }
+                }
+            }),
+            Prev::Many(operands),
+            Op::CrossEntropy,
+            None,
+        )
+    }
+}
+
+// ln(Σ exp(xᵢ)), shifted by the maximum.
+fn log_sum_exp(x: &[f64]) -> f64 {
+    let max = x.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+    max + x.iter().map(|x_i| (x_i - max).exp()).sum::<f64>().ln()
+}
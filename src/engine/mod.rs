@@ -1,7 +1,12 @@
 mod actv_fns;
 mod backprop;
 mod comp_ops;
+mod expr;
+mod fused_ops;
+mod parse;
 mod prim_ops;
 mod value;
 
+pub use expr::Expr;
+pub use parse::{parse, ParseError};
 pub use value::{ActvFn, Op, Value};
@@ -0,0 +1,403 @@
+use crate::engine::value::{ActvFn, Value};
+use std::{borrow, collections::HashMap, error::Error, fmt, hash::Hash};
+
+/**
+Builds a `Value` graph from an infix expression such as `x^2 + exp(-y) * relu(z)`.
+- `src`: Expression to parse.
+- `bindings`: Values that the variables in `src` refer to, by name.
+
+Supports `+`, `-`, `*`, `/`, `^` with a constant exponent, parentheses, `ln`, `exp` and the
+activation functions, by their method names (`relu`, `leaky_relu`, `gelu_tanh`, `sigmoid`, ...)
+or by their `Op` symbols (`ReLU`, `LeakyReLU`, `GELUTanh`, `σ`, ...). `leaky_relu(x)` has a slope
+of 0.01, and `LeakyReLU(0.2)(x)`, as `Op` prints it, a slope of 0.2. Numeric literals are
+constants, so `x * 2` builds the same graph as `&x * 2.0`.
+*/
+pub fn parse<K>(src: &str, bindings: &HashMap<K, Value>) -> Result<Value, ParseError>
+where
+    K: borrow::Borrow<str> + Hash + Eq,
+{
+    let tokens = tokenize(src)?;
+    let mut parser = Parser {
+        tokens,
+        pos: 0,
+        bindings,
+    };
+
+    let expr = parser.expr()?;
+    let (token, position) = parser.peek();
+    if *token != Token::End {
+        return Err(ParseError::new(position, format!("unexpected {}", token)));
+    }
+
+    Ok(match expr {
+        Operand::Val(value) => value,
+        Operand::Num(num) => Value::new_const(num),
+    })
+}
+
+/// Error in parsing an expression, with the position of the offending character.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    pub position: usize,
+    pub message: String,
+}
+
+impl ParseError {
+    fn new(position: usize, message: String) -> ParseError {
+        ParseError { position, message }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at position {}", self.message, self.position)
+    }
+}
+
+impl Error for ParseError {}
+
+// Tokens
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Num(f64),
+    Ident(String),
+    Symbol(char),
+    End,
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Token::Num(num) => write!(f, "number `{}`", num),
+            Token::Ident(ident) => write!(f, "identifier `{}`", ident),
+            Token::Symbol(symbol) => write!(f, "`{}`", symbol),
+            Token::End => write!(f, "end of input"),
+        }
+    }
+}
+
+fn tokenize(src: &str) -> Result<Vec<(Token, usize)>, ParseError> {
+    let chars: Vec<char> = src.chars().collect();
+    let mut tokens = vec![];
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let start = i;
+
+        if c.is_whitespace() {
+            i += 1;
+        } else if c.is_ascii_digit() || c == '.' {
+            while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                i += 1;
+            }
+            // Exponent, as in 1e-3
+            if i < chars.len() && (chars[i] == 'e' || chars[i] == 'E') {
+                let mut j = i + 1;
+                if j < chars.len() && (chars[j] == '+' || chars[j] == '-') {
+                    j += 1;
+                }
+                if j < chars.len() && chars[j].is_ascii_digit() {
+                    i = j;
+                    while i < chars.len() && chars[i].is_ascii_digit() {
+                        i += 1;
+                    }
+                }
+            }
+
+            let literal: String = chars[start..i].iter().collect();
+            let num = literal
+                .parse::<f64>()
+                .map_err(|_| ParseError::new(start, format!("invalid number `{}`", literal)))?;
+            tokens.push((Token::Num(num), start));
+        } else if c.is_alphabetic() || c == '_' {
+            while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            tokens.push((Token::Ident(chars[start..i].iter().collect()), start));
+        } else if "+-*/^()".contains(c) {
+            tokens.push((Token::Symbol(c), start));
+            i += 1;
+        } else {
+            return Err(ParseError::new(
+                start,
+                format!("unexpected character `{}`", c),
+            ));
+        }
+    }
+
+    tokens.push((Token::End, chars.len()));
+    Ok(tokens)
+}
+
+// Parser
+
+// Constants are kept as f64 until they meet a Value, to use the f64 variants of the ops.
+enum Operand {
+    Num(f64),
+    Val(Value),
+}
+
+struct Parser<'a, K> {
+    tokens: Vec<(Token, usize)>,
+    pos: usize,
+    bindings: &'a HashMap<K, Value>,
+}
+
+impl<K> Parser<'_, K>
+where
+    K: borrow::Borrow<str> + Hash + Eq,
+{
+    fn peek(&self) -> (&Token, usize) {
+        let (token, position) = &self.tokens[self.pos];
+        (token, *position)
+    }
+
+    fn next(&mut self) -> (Token, usize) {
+        let next = self.tokens[self.pos].clone();
+        if next.0 != Token::End {
+            self.pos += 1;
+        }
+        next
+    }
+
+    fn eat(&mut self, symbol: char) -> bool {
+        if *self.peek().0 == Token::Symbol(symbol) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, symbol: char) -> Result<(), ParseError> {
+        let (token, position) = self.next();
+        if token == Token::Symbol(symbol) {
+            Ok(())
+        } else {
+            Err(ParseError::new(
+                position,
+                format!("expected `{}`, found {}", symbol, token),
+            ))
+        }
+    }
+
+    // expr := term (('+' | '-') term)*
+    fn expr(&mut self) -> Result<Operand, ParseError> {
+        let mut lhs = self.term()?;
+        loop {
+            if self.eat('+') {
+                lhs = add(lhs, self.term()?);
+            } else if self.eat('-') {
+                lhs = add(lhs, neg(self.term()?));
+            } else {
+                return Ok(lhs);
+            }
+        }
+    }
+
+    // term := unary (('*' | '/') unary)*
+    fn term(&mut self) -> Result<Operand, ParseError> {
+        let mut lhs = self.unary()?;
+        loop {
+            if self.eat('*') {
+                lhs = mul(lhs, self.unary()?);
+            } else if self.eat('/') {
+                lhs = div(lhs, self.unary()?);
+            } else {
+                return Ok(lhs);
+            }
+        }
+    }
+
+    // unary := ('-' | '+') unary | power
+    fn unary(&mut self) -> Result<Operand, ParseError> {
+        if self.eat('-') {
+            Ok(neg(self.unary()?))
+        } else if self.eat('+') {
+            self.unary()
+        } else {
+            self.power()
+        }
+    }
+
+    // power := primary ('^' unary)?
+    fn power(&mut self) -> Result<Operand, ParseError> {
+        let base = self.primary()?;
+        if !self.eat('^') {
+            return Ok(base);
+        }
+
+        let position = self.peek().1;
+        match (base, self.unary()?) {
+            (Operand::Num(base), Operand::Num(power)) => Ok(Operand::Num(base.powf(power))),
+            (Operand::Val(base), Operand::Num(power)) => Ok(Operand::Val(base.pow(power))),
+            (_, Operand::Val(_)) => Err(ParseError::new(
+                position,
+                "exponent must be a constant".to_string(),
+            )),
+        }
+    }
+
+    // primary := number | ident '(' expr ')' | ident | '(' expr ')'
+    fn primary(&mut self) -> Result<Operand, ParseError> {
+        let (token, position) = self.next();
+
+        match token {
+            Token::Num(num) => Ok(Operand::Num(num)),
+            Token::Ident(name) if self.eat('(') => {
+                let arg_position = self.peek().1;
+                let arg = self.expr()?;
+                self.expect(')')?;
+
+                // LeakyReLU(slope)(x), with a constant slope.
+                if is_leaky_relu(&name) && self.eat('(') {
+                    let slope = match arg {
+                        Operand::Num(slope) => slope,
+                        Operand::Val(_) => {
+                            return Err(ParseError::new(
+                                arg_position,
+                                "slope must be a constant".to_string(),
+                            ))
+                        }
+                    };
+                    let arg = self.expr()?;
+                    self.expect(')')?;
+                    return Ok(leaky_relu(arg, slope));
+                }
+
+                call(&name, arg).ok_or_else(|| {
+                    ParseError::new(position, format!("unknown function `{}`", name))
+                })
+            }
+            Token::Ident(name) => match self.bindings.get(name.as_str()) {
+                Some(value) => Ok(Operand::Val(value.clone())),
+                None => Err(ParseError::new(
+                    position,
+                    format!("unknown variable `{}`", name),
+                )),
+            },
+            Token::Symbol('(') => {
+                let expr = self.expr()?;
+                self.expect(')')?;
+                Ok(expr)
+            }
+            token => Err(ParseError::new(
+                position,
+                format!("expected an operand, found {}", token),
+            )),
+        }
+    }
+}
+
+fn call(name: &str, arg: Operand) -> Option<Operand> {
+    let f = match name.to_lowercase().as_str() {
+        "ln" => Value::ln,
+        "exp" => Value::exp,
+        "relu" => Value::relu,
+        "leaky_relu" | "leakyrelu" => Value::leaky_relu,
+        "tanh" => Value::tanh,
+        "sigmoid" | "σ" => Value::sigmoid,
+        "gelu" => Value::gelu,
+        "gelu_tanh" | "gelutanh" => Value::gelu_tanh,
+        "silu" | "swish" => Value::silu,
+        "elu" => Value::elu,
+        "selu" => Value::selu,
+        "softplus" => Value::softplus,
+        "softsign" => Value::softsign,
+        "mish" => Value::mish,
+        "hard_sigmoid" | "hardsigmoid" => Value::hard_sigmoid,
+        "hard_tanh" | "hardtanh" => Value::hard_tanh,
+        _ => return None,
+    };
+
+    Some(match arg {
+        Operand::Num(num) => Operand::Num(f(&Value::new_const(num)).borrow().data),
+        Operand::Val(value) => Operand::Val(f(&value)),
+    })
+}
+
+fn is_leaky_relu(name: &str) -> bool {
+    matches!(name.to_lowercase().as_str(), "leaky_relu" | "leakyrelu")
+}
+
+fn leaky_relu(arg: Operand, slope: f64) -> Operand {
+    let actv_fn = ActvFn::LeakyReLU(slope);
+    match arg {
+        Operand::Num(num) => Operand::Num(actv_fn.apply(num)),
+        Operand::Val(value) => Operand::Val(value.activate(actv_fn)),
+    }
+}
+
+fn add(lhs: Operand, rhs: Operand) -> Operand {
+    match (lhs, rhs) {
+        (Operand::Num(l), Operand::Num(r)) => Operand::Num(l + r),
+        (Operand::Val(l), Operand::Num(r)) => Operand::Val(l + r),
+        (Operand::Num(l), Operand::Val(r)) => Operand::Val(l + r),
+        (Operand::Val(l), Operand::Val(r)) => Operand::Val(l + r),
+    }
+}
+
+fn mul(lhs: Operand, rhs: Operand) -> Operand {
+    match (lhs, rhs) {
+        (Operand::Num(l), Operand::Num(r)) => Operand::Num(l * r),
+        (Operand::Val(l), Operand::Num(r)) => Operand::Val(l * r),
+        (Operand::Num(l), Operand::Val(r)) => Operand::Val(l * r),
+        (Operand::Val(l), Operand::Val(r)) => Operand::Val(l * r),
+    }
+}
+
+fn div(lhs: Operand, rhs: Operand) -> Operand {
+    match (lhs, rhs) {
+        (Operand::Num(l), Operand::Num(r)) => Operand::Num(l / r),
+        (Operand::Val(l), Operand::Num(r)) => Operand::Val(l / r),
+        (Operand::Num(l), Operand::Val(r)) => Operand::Val(l / r),
+        (Operand::Val(l), Operand::Val(r)) => Operand::Val(l / r),
+    }
+}
+
+fn neg(operand: Operand) -> Operand {
+    match operand {
+        Operand::Num(num) => Operand::Num(-num),
+        Operand::Val(value) => Operand::Val(-value),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn leaky_relu_slope_round_trips() {
+        let x = Value::new(-2.0).with_name('x');
+        let y = (&x * 3.0).activate(ActvFn::LeakyReLU(0.2));
+        assert_eq!(y.expr().to_string(), "LeakyReLU(0.2)(x * 3)");
+
+        let bindings = HashMap::from([("x", x.clone())]);
+        let parsed = parse(&y.expr().to_string(), &bindings).expect("Error in parsing");
+        assert_eq!(parsed.expr().to_string(), y.expr().to_string());
+        assert!((parsed.borrow().data - -1.2).abs() < 1e-12);
+
+        parsed.backward();
+        assert!((x.borrow().grad - 0.6).abs() < 1e-12);
+    }
+
+    #[test]
+    fn leaky_relu_default_slope() {
+        let bindings: HashMap<&str, Value> = HashMap::new();
+        let y = parse("leaky_relu(-1)", &bindings).expect("Error in parsing");
+        assert!((y.borrow().data - -0.01).abs() < 1e-12);
+
+        let y = parse("LeakyReLU(0.5)(-1)", &bindings).expect("Error in parsing");
+        assert!((y.borrow().data - -0.5).abs() < 1e-12);
+    }
+
+    #[test]
+    fn leaky_relu_slope_must_be_constant() {
+        let bindings = HashMap::from([("a", Value::new(0.1))]);
+        let err = parse("LeakyReLU(a)(1)", &bindings).expect_err("Parsed a variable slope");
+        assert_eq!(err.message, "slope must be a constant");
+        assert_eq!(err.position, 10);
+    }
+}
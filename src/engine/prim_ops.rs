@@ -38,20 +38,20 @@ fn add(self: f64, rhs: Value) -> Value {
 
 fn add_backward(value: &V) {
     if let Prev::Binary(l, r) = &value.prev {
-        l.borrow_mut().grad += value.grad;
-        r.borrow_mut().grad += value.grad;
+        l.accumulate_grad(value.grad);
+        r.accumulate_grad(value.grad);
     }
 }
 
 fn add_backward_lhs(value: &V) {
     if let Prev::Binary(l, _) = &value.prev {
-        l.borrow_mut().grad += value.grad;
+        l.accumulate_grad(value.grad);
     }
 }
 
 fn add_backward_rhs(value: &V) {
     if let Prev::Binary(_, r) = &value.prev {
-        r.borrow_mut().grad += value.grad;
+        r.accumulate_grad(value.grad);
     }
 }
 
@@ -94,22 +94,22 @@ fn mul_backward(value: &V) {
     if let Prev::Binary(l, r) = &value.prev {
         let l_data = l.borrow().data;
         let r_data = r.borrow().data;
-        l.borrow_mut().grad += r_data * value.grad;
-        r.borrow_mut().grad += l_data * value.grad;
+        l.accumulate_grad(r_data * value.grad);
+        r.accumulate_grad(l_data * value.grad);
     }
 }
 
 fn mul_backward_lhs(value: &V) {
     if let Prev::Binary(l, r) = &value.prev {
         let r_data = r.borrow().data;
-        l.borrow_mut().grad += r_data * value.grad;
+        l.accumulate_grad(r_data * value.grad);
     }
 }
 
 fn mul_backward_rhs(value: &V) {
     if let Prev::Binary(l, r) = &value.prev {
         let l_data = l.borrow().data;
-        r.borrow_mut().grad += l_data * value.grad;
+        r.accumulate_grad(l_data * value.grad);
     }
 }
 
@@ -123,7 +123,7 @@ impl Value {
                 if let Prev::Binary(a, b) = &value.prev {
                     let base = a.borrow().data;
                     let power = b.borrow().data;
-                    a.borrow_mut().grad += power * base.powf(power - 1.0) * value.grad;
+                    a.accumulate_grad(power * base.powf(power - 1.0) * value.grad);
                 }
             }),
             Prev::Binary(self.clone(), Value::new_const(power)),
@@ -137,8 +137,8 @@ impl Value {
             self.borrow().data.ln(),
             Some(|value: &V| {
                 if let Prev::Unary(a) = &value.prev {
-                    let mut prev = a.borrow_mut();
-                    prev.grad += value.grad / prev.data;
+                    let data = a.borrow().data;
+                    a.accumulate_grad(value.grad / data);
                 }
             }),
             Prev::Unary(self.clone()),
@@ -152,7 +152,7 @@ impl Value {
             self.borrow().data.exp(),
             Some(|value: &V| {
                 if let Prev::Unary(a) = &value.prev {
-                    a.borrow_mut().grad += value.data * value.grad;
+                    a.accumulate_grad(value.data * value.grad);
                 }
             }),
             Prev::Unary(self.clone()),
@@ -12,6 +12,7 @@ pub struct V {
     pub(crate) op: Op,
     pub(crate) uuid: Uuid,
     pub(crate) name: Option<char>,
+    pub(crate) frozen: bool,
 }
 
 #[derive(Debug)]
@@ -19,6 +20,8 @@ pub enum Prev {
     Init,
     Unary(Value),
     Binary(Value, Value),
+    /// Operands of fused ops, such as the logits and targets of `cross_entropy_with_logits`.
+    Many(Vec<Value>),
 }
 
 #[derive(Debug)]
@@ -29,16 +32,40 @@ pub enum Op {
     Ln,
     Exp,
     ActvFn(ActvFn),
+    CrossEntropy,
     Var,
     Const,
 }
 
+#[allow(clippy::upper_case_acronyms)]
 #[derive(Debug, Clone, Copy)]
 pub enum ActvFn {
     ReLU,
-    LeakyReLU,
+    /// LeakyReLU(slope), with the slope of the negative part.
+    LeakyReLU(f64),
     Tanh,
     Sigmoid,
+    /// Exact GELU, `x Φ(x)`.
+    GELU,
+    /// GELU with the tanh approximation of `Φ(x)`.
+    GELUTanh,
+    /// SiLU or Swish, `x σ(x)`.
+    SiLU,
+    ELU,
+    SELU,
+    Softplus,
+    Softsign,
+    Mish,
+    HardSigmoid,
+    HardTanh,
+    /// PReLU(initial slope), only in a `Neuron`, which learns the slope. See `Value::prelu`.
+    PReLU(f64),
+    /// User-defined activation function, with its name for `Display` and its derivative.
+    Custom {
+        name: &'static str,
+        forward: fn(f64) -> f64,
+        derivative: fn(f64) -> f64,
+    },
 }
 
 impl Value {
@@ -57,6 +84,7 @@ impl Value {
             op,
             uuid: Uuid::new_v4(),
             name,
+            frozen: false,
         })))
     }
 
@@ -88,6 +116,20 @@ impl Value {
         self.borrow_mut().name = Some(name);
         self
     }
+
+    /// Stop accumulating gradients into the Value during `backward`, so that optimisers leave
+    /// it unchanged.
+    pub fn freeze(&self) {
+        self.borrow_mut().frozen = true;
+    }
+
+    pub fn unfreeze(&self) {
+        self.borrow_mut().frozen = false;
+    }
+
+    pub fn is_frozen(&self) -> bool {
+        self.borrow().frozen
+    }
 }
 
 // val.0.borrow() becomes val.borrow()
@@ -172,16 +214,33 @@ impl fmt::Display for Value {
 
 impl fmt::Display for Op {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Op::ActvFn(ActvFn::LeakyReLU(slope)) => return write!(f, "LeakyReLU({})", slope),
+            Op::ActvFn(ActvFn::PReLU(slope)) => return write!(f, "PReLU({})", slope),
+            _ => {}
+        }
+
         let symbol = match self {
             Op::Add => "+",
             Op::Mul => "*",
             Op::Pow => "^",
             Op::Ln => "ln",
             Op::Exp => "exp",
+            Op::CrossEntropy => "cross_entropy",
             Op::ActvFn(ActvFn::ReLU) => "ReLU",
-            Op::ActvFn(ActvFn::LeakyReLU) => "LeakyReLU",
             Op::ActvFn(ActvFn::Tanh) => "tanh",
             Op::ActvFn(ActvFn::Sigmoid) => "σ",
+            Op::ActvFn(ActvFn::GELU) => "GELU",
+            Op::ActvFn(ActvFn::GELUTanh) => "GELUTanh",
+            Op::ActvFn(ActvFn::SiLU) => "SiLU",
+            Op::ActvFn(ActvFn::ELU) => "ELU",
+            Op::ActvFn(ActvFn::SELU) => "SELU",
+            Op::ActvFn(ActvFn::Softplus) => "softplus",
+            Op::ActvFn(ActvFn::Softsign) => "softsign",
+            Op::ActvFn(ActvFn::Mish) => "Mish",
+            Op::ActvFn(ActvFn::HardSigmoid) => "HardSigmoid",
+            Op::ActvFn(ActvFn::HardTanh) => "HardTanh",
+            Op::ActvFn(ActvFn::Custom { name, .. }) => name,
             _ => "",
         };
 
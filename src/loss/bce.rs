@@ -1,31 +1,53 @@
-use crate::engine::Value;
+use crate::{
+    engine::Value,
+    loss::{labels::binary_label_targets, ClassificationLoss, Loss, LossOptions},
+};
 
 /** Binary Cross-Entropy loss
-For binary classification, when targets are -1 and 1.*/
+For binary classification, on probabilities such as `sigmoid` of the outputs, when targets are
+0 and 1. Probabilities of exactly 0 or 1 give infinite losses, `BinaryCrossEntropyWithLogitsLoss`
+is stable on the raw outputs. The loss of a sample is the mean over its outputs.*/
 #[derive(Debug)]
-pub struct BinaryCrossEntropyLoss;
+pub struct BinaryCrossEntropyLoss {
+    options: LossOptions,
+}
 
 impl BinaryCrossEntropyLoss {
     pub fn new() -> BinaryCrossEntropyLoss {
-        BinaryCrossEntropyLoss
+        BinaryCrossEntropyLoss {
+            options: LossOptions::default(),
+        }
     }
+}
 
-    pub fn loss(&self, ypred: &[Vec<Value>], ytrue: &[Vec<Value>]) -> Value {
-        -ypred
+impl Loss for BinaryCrossEntropyLoss {
+    fn sample_losses(&self, ypred: &[Vec<Value>], ytrue: &[Vec<Value>]) -> Vec<Value> {
+        check_binary_targets(ytrue);
+
+        ypred
             .iter()
             .zip(ytrue)
             .map(|(ypred_i, ytrue_i)| {
-                ypred_i
-                    .iter()
-                    .zip(ytrue_i)
-                    .map(|(ypred_j, ytrue_j)| {
-                        ytrue_j * ypred_j.ln()
-                            + (1.0 - ytrue_j) * (1.0 - ypred_j).ln()
+                self.options
+                    .mean_over_outputs(ypred_i, ytrue_i, |ypred_j, ytrue_j| {
+                        -(ytrue_j * ypred_j.ln() + (1.0 - ytrue_j) * (1.0 - ypred_j).ln())
                     })
-                    .sum::<Value>()
             })
-            .sum::<Value>()
-            / (ypred.len() * ypred[0].len()) as f64
+            .collect()
+    }
+
+    fn options(&self) -> &LossOptions {
+        &self.options
+    }
+
+    fn options_mut(&mut self) -> &mut LossOptions {
+        &mut self.options
+    }
+}
+
+impl ClassificationLoss for BinaryCrossEntropyLoss {
+    fn label_targets(&self, labels: &[usize], n_outputs: usize) -> Vec<Vec<Value>> {
+        binary_label_targets(labels, n_outputs, self.options.label_smoothing)
     }
 }
 
@@ -34,3 +56,15 @@ impl Default for BinaryCrossEntropyLoss {
         Self::new()
     }
 }
+
+// Targets of the binary cross-entropy are probabilities, 0 and 1 or soft labels in between.
+pub(crate) fn check_binary_targets(ytrue: &[Vec<Value>]) {
+    for ytrue_j in ytrue.iter().flatten() {
+        let t = ytrue_j.borrow().data;
+        assert!(
+            (0.0..=1.0).contains(&t),
+            "Targets of the binary cross-entropy must be in [0, 1], found {}",
+            t
+        );
+    }
+}
@@ -0,0 +1,71 @@
+use crate::{
+    engine::Value,
+    loss::{
+        bce::check_binary_targets, labels::binary_label_targets, ClassificationLoss, Loss,
+        LossOptions,
+    },
+};
+
+/** Binary Cross-Entropy loss from logits
+For binary classification, on the raw outputs of the model instead of `sigmoid` of them, when
+targets are 0 and 1. Computed as `(1 - t) x + (1 + (pos_weight - 1) t) softplus(-x)`, which
+neither overflows nor takes `ln(0)` for saturated outputs. The loss of a sample is the mean over
+its outputs.
+- `pos_weight`: Weight of the positive targets, above 1 to favour recall on imbalanced data.*/
+#[derive(Debug)]
+pub struct BinaryCrossEntropyWithLogitsLoss {
+    pub pos_weight: f64,
+    options: LossOptions,
+}
+
+impl BinaryCrossEntropyWithLogitsLoss {
+    pub fn new() -> BinaryCrossEntropyWithLogitsLoss {
+        BinaryCrossEntropyWithLogitsLoss {
+            pos_weight: 1.0,
+            options: LossOptions::default(),
+        }
+    }
+
+    /// Weighs the loss of the positive targets by `pos_weight`.
+    pub fn with_pos_weight(mut self, pos_weight: f64) -> BinaryCrossEntropyWithLogitsLoss {
+        assert!(pos_weight > 0.0, "pos_weight must be positive");
+        self.pos_weight = pos_weight;
+        self
+    }
+}
+
+impl Loss for BinaryCrossEntropyWithLogitsLoss {
+    fn sample_losses(&self, logits: &[Vec<Value>], ytrue: &[Vec<Value>]) -> Vec<Value> {
+        check_binary_targets(ytrue);
+
+        logits
+            .iter()
+            .zip(ytrue)
+            .map(|(logits_i, ytrue_i)| {
+                self.options.mean_over_outputs(logits_i, ytrue_i, |x, t| {
+                    (1.0 - t) * x + (1.0 + (self.pos_weight - 1.0) * t) * (-x).softplus()
+                })
+            })
+            .collect()
+    }
+
+    fn options(&self) -> &LossOptions {
+        &self.options
+    }
+
+    fn options_mut(&mut self) -> &mut LossOptions {
+        &mut self.options
+    }
+}
+
+impl ClassificationLoss for BinaryCrossEntropyWithLogitsLoss {
+    fn label_targets(&self, labels: &[usize], n_outputs: usize) -> Vec<Vec<Value>> {
+        binary_label_targets(labels, n_outputs, self.options.label_smoothing)
+    }
+}
+
+impl Default for BinaryCrossEntropyWithLogitsLoss {
+    fn default() -> Self {
+        Self::new()
+    }
+}
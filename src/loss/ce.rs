@@ -1,31 +1,55 @@
-use crate::engine::Value;
+use crate::{
+    engine::Value,
+    loss::{ClassificationLoss, Loss, LossOptions},
+};
 
 /** Cross-Entropy loss
-For multiclass and multilabel classification.*/
+For multiclass and multilabel classification, on probabilities such as `softmax` of the outputs.
+The loss of a sample is the sum over the classes of `-wⱼ tⱼ ln(pⱼ)`.*/
 #[derive(Debug)]
-pub struct CrossEntropyLoss;
+pub struct CrossEntropyLoss {
+    options: LossOptions,
+}
 
 impl CrossEntropyLoss {
     pub fn new() -> CrossEntropyLoss {
-        CrossEntropyLoss
+        CrossEntropyLoss {
+            options: LossOptions::default(),
+        }
     }
+}
 
-    pub fn loss(&self, ypred: &[Vec<Value>], ytrue: &[Vec<Value>]) -> Value {
-        -ypred
+impl Loss for CrossEntropyLoss {
+    fn sample_losses(&self, ypred: &[Vec<Value>], ytrue: &[Vec<Value>]) -> Vec<Value> {
+        ypred
             .iter()
             .zip(ytrue)
             .map(|(ypred_i, ytrue_i)| {
-                ypred_i
+                self.options.check_outputs(ypred_i.len());
+
+                -ypred_i
                     .iter()
                     .zip(ytrue_i)
-                    .map(|(ypred_j, ytrue_j)| ytrue_j * ypred_j.ln())
+                    .enumerate()
+                    .map(|(j, (ypred_j, ytrue_j))| {
+                        ytrue_j * ypred_j.ln() * self.options.class_weight(j)
+                    })
                     .sum::<Value>()
             })
-            .sum::<Value>()
-            / (ypred.len() * ypred[0].len()) as f64
+            .collect()
+    }
+
+    fn options(&self) -> &LossOptions {
+        &self.options
+    }
+
+    fn options_mut(&mut self) -> &mut LossOptions {
+        &mut self.options
     }
 }
 
+impl ClassificationLoss for CrossEntropyLoss {}
+
 impl Default for CrossEntropyLoss {
     fn default() -> Self {
         Self::new()
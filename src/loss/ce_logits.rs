@@ -0,0 +1,58 @@
+use crate::{
+    engine::Value,
+    loss::{ClassificationLoss, Loss, LossOptions},
+};
+
+/** Cross-Entropy loss from logits
+For multiclass classification, on the raw outputs of the model instead of `softmax` of them.
+Fuses log-softmax and cross-entropy into one numerically stable op per sample. Class weights
+scale the targets of the op.*/
+#[derive(Debug)]
+pub struct CrossEntropyWithLogitsLoss {
+    options: LossOptions,
+}
+
+impl CrossEntropyWithLogitsLoss {
+    pub fn new() -> CrossEntropyWithLogitsLoss {
+        CrossEntropyWithLogitsLoss {
+            options: LossOptions::default(),
+        }
+    }
+}
+
+impl Loss for CrossEntropyWithLogitsLoss {
+    fn sample_losses(&self, logits: &[Vec<Value>], ytrue: &[Vec<Value>]) -> Vec<Value> {
+        logits
+            .iter()
+            .zip(ytrue)
+            .map(|(logits_i, ytrue_i)| match &self.options.class_weights {
+                Some(weights) => {
+                    self.options.check_outputs(logits_i.len());
+                    let ytrue_i: Vec<Value> = ytrue_i
+                        .iter()
+                        .zip(weights)
+                        .map(|(ytrue_j, w)| Value::new_const(ytrue_j.borrow().data * w))
+                        .collect();
+                    Value::cross_entropy_with_logits(logits_i, &ytrue_i)
+                }
+                None => Value::cross_entropy_with_logits(logits_i, ytrue_i),
+            })
+            .collect()
+    }
+
+    fn options(&self) -> &LossOptions {
+        &self.options
+    }
+
+    fn options_mut(&mut self) -> &mut LossOptions {
+        &mut self.options
+    }
+}
+
+impl ClassificationLoss for CrossEntropyWithLogitsLoss {}
+
+impl Default for CrossEntropyWithLogitsLoss {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,202 @@
+use crate::{engine::Value, loss::labels::smoothed_one_hot};
+
+/// How the losses of the samples of a batch are combined.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Reduction {
+    /// Mean of the losses, weighted by the sample weights if any.
+    #[default]
+    Mean,
+    /// Sum of the losses.
+    Sum,
+    /// One loss per sample, through `losses`.
+    None,
+}
+
+/**
+Settings shared by the loss criterions.
+- `reduction`: How the losses of the samples are combined, `Mean` by default.
+- `class_weights`: Weight of each output, or class, in the loss of a sample.
+- `label_smoothing`: Share of the targets from integer labels spread uniformly over the classes,
+  for a `ClassificationLoss`.
+*/
+#[derive(Debug, Clone, Default)]
+pub struct LossOptions {
+    pub reduction: Reduction,
+    pub class_weights: Option<Vec<f64>>,
+    pub label_smoothing: f64,
+}
+
+impl LossOptions {
+    // Weight of the output `j`, 1 without class weights.
+    pub(crate) fn class_weight(&self, j: usize) -> f64 {
+        self.class_weights
+            .as_ref()
+            .map_or(1.0, |weights| weights[j])
+    }
+
+    // Mean over the outputs of a sample of `f(ypred_j, ytrue_j)`, weighted by the class weights.
+    pub(crate) fn mean_over_outputs<F>(&self, ypred_i: &[Value], ytrue_i: &[Value], f: F) -> Value
+    where
+        F: Fn(&Value, &Value) -> Value,
+    {
+        self.check_outputs(ypred_i.len());
+
+        ypred_i
+            .iter()
+            .zip(ytrue_i)
+            .enumerate()
+            .map(|(j, (ypred_j, ytrue_j))| match &self.class_weights {
+                Some(weights) => f(ypred_j, ytrue_j) * weights[j],
+                None => f(ypred_j, ytrue_j),
+            })
+            .sum::<Value>()
+            / ypred_i.len() as f64
+    }
+
+    pub(crate) fn check_outputs(&self, n_outputs: usize) {
+        if let Some(weights) = &self.class_weights {
+            assert!(
+                weights.len() == n_outputs,
+                "Expected {} class weights, found {}",
+                n_outputs,
+                weights.len()
+            );
+        }
+    }
+}
+
+/**
+Loss criterion, computed per sample and combined according to the `Reduction` of its options.
+Implementors only compute the losses of the samples, with the class weights applied.
+*/
+pub trait Loss {
+    /// Loss of each sample, or row, of the batch, before the reduction.
+    fn sample_losses(&self, ypred: &[Vec<Value>], ytrue: &[Vec<Value>]) -> Vec<Value>;
+
+    fn options(&self) -> &LossOptions;
+
+    fn options_mut(&mut self) -> &mut LossOptions;
+
+    fn with_reduction(mut self, reduction: Reduction) -> Self
+    where
+        Self: Sized,
+    {
+        self.options_mut().reduction = reduction;
+        self
+    }
+
+    /// Weighs the loss of each output, or class, for imbalanced datasets.
+    fn with_class_weights(mut self, class_weights: Vec<f64>) -> Self
+    where
+        Self: Sized,
+    {
+        self.options_mut().class_weights = Some(class_weights);
+        self
+    }
+
+    /// Reduced loss of the batch. Panics with `Reduction::None`, which needs `losses`.
+    fn loss(&self, ypred: &[Vec<Value>], ytrue: &[Vec<Value>]) -> Value {
+        check_reduced(self.options().reduction);
+        self.losses(ypred, ytrue).remove(0)
+    }
+
+    /// Losses of the batch, one per sample with `Reduction::None` and one in total otherwise.
+    fn losses(&self, ypred: &[Vec<Value>], ytrue: &[Vec<Value>]) -> Vec<Value> {
+        let losses = self.sample_losses(ypred, ytrue);
+        let n = losses.len() as f64;
+        reduce(self.options().reduction, losses, n)
+    }
+
+    /**
+    Reduced loss of the batch, with the loss of each sample weighted by `sample_weights`. The mean
+    is divided by the sum of the weights.
+    */
+    fn weighted_loss(
+        &self,
+        ypred: &[Vec<Value>],
+        ytrue: &[Vec<Value>],
+        sample_weights: &[f64],
+    ) -> Value {
+        check_reduced(self.options().reduction);
+        self.weighted_losses(ypred, ytrue, sample_weights).remove(0)
+    }
+
+    /// `losses`, with the loss of each sample weighted by `sample_weights`.
+    fn weighted_losses(
+        &self,
+        ypred: &[Vec<Value>],
+        ytrue: &[Vec<Value>],
+        sample_weights: &[f64],
+    ) -> Vec<Value> {
+        assert!(
+            sample_weights.len() == ypred.len(),
+            "Expected {} sample weights, found {}",
+            ypred.len(),
+            sample_weights.len()
+        );
+
+        let losses = self
+            .sample_losses(ypred, ytrue)
+            .into_iter()
+            .zip(sample_weights)
+            .map(|(loss, weight)| loss * *weight)
+            .collect();
+        reduce(
+            self.options().reduction,
+            losses,
+            sample_weights.iter().sum(),
+        )
+    }
+}
+
+/**
+Loss criterion for classification, which also takes integer class labels instead of rows of
+targets. The labels are turned into targets by `label_targets`, one-hot rows by default.
+*/
+pub trait ClassificationLoss: Loss {
+    /// Targets of integer class labels, for `n_outputs` outputs per sample, with label smoothing.
+    fn label_targets(&self, labels: &[usize], n_outputs: usize) -> Vec<Vec<Value>> {
+        smoothed_one_hot(labels, n_outputs, self.options().label_smoothing)
+    }
+
+    /// Smooths the targets built from integer labels, `1 - smoothing + smoothing / K` for the
+    /// label and `smoothing / K` for the other `K - 1` classes.
+    fn with_label_smoothing(mut self, smoothing: f64) -> Self
+    where
+        Self: Sized,
+    {
+        assert!(
+            (0.0..1.0).contains(&smoothing),
+            "Label smoothing must be in [0, 1)"
+        );
+        self.options_mut().label_smoothing = smoothing;
+        self
+    }
+
+    /// `loss`, for integer class labels instead of rows of targets.
+    fn loss_from_labels(&self, ypred: &[Vec<Value>], labels: &[usize]) -> Value {
+        check_reduced(self.options().reduction);
+        self.losses_from_labels(ypred, labels).remove(0)
+    }
+
+    /// `losses`, for integer class labels instead of rows of targets.
+    fn losses_from_labels(&self, ypred: &[Vec<Value>], labels: &[usize]) -> Vec<Value> {
+        let n_outputs = ypred.first().map_or(0, |ypred_i| ypred_i.len());
+        self.losses(ypred, &self.label_targets(labels, n_outputs))
+    }
+}
+
+fn reduce(reduction: Reduction, losses: Vec<Value>, total_weight: f64) -> Vec<Value> {
+    match reduction {
+        Reduction::Mean => vec![losses.into_iter().sum::<Value>() / total_weight],
+        Reduction::Sum => vec![losses.into_iter().sum::<Value>()],
+        Reduction::None => losses,
+    }
+}
+
+fn check_reduced(reduction: Reduction) {
+    assert!(
+        reduction != Reduction::None,
+        "Reduction::None gives one loss per sample, use `losses` instead of `loss`"
+    );
+}
@@ -0,0 +1,128 @@
+use crate::{
+    engine::Value,
+    loss::{ClassificationLoss, Loss, LossOptions},
+    nn::log_softmax,
+};
+
+const FOCUS_EPS: f64 = 1e-12;
+
+/** Focal loss
+For multiclass classification with imbalanced classes, on the raw outputs of the model. Lin et
+al. scale the cross-entropy of each class by `(1 - pⱼ)^gamma`, so that well classified samples
+contribute less and the training focuses on hard ones. The loss of a sample is the sum over the
+classes of `-wⱼ tⱼ (1 - pⱼ)^gamma log(pⱼ)`, with `log(pⱼ)` from `log_softmax`. Class weights
+play the role of the `alpha` of the paper.
+- `gamma`: Focusing parameter, 0 for the cross-entropy.*/
+#[derive(Debug)]
+pub struct FocalLoss {
+    pub gamma: f64,
+    options: LossOptions,
+}
+
+impl FocalLoss {
+    pub fn new(gamma: f64) -> FocalLoss {
+        assert!(gamma >= 0.0, "gamma must be non-negative");
+        FocalLoss {
+            gamma,
+            options: LossOptions::default(),
+        }
+    }
+}
+
+impl FocalLoss {
+    // (1 - p)^gamma, none for a gamma of 0. 1 - p is clamped from below, as a constant, so that
+    // the derivative of the power stays finite when p saturates to 1.
+    fn focus(&self, log_prob: &Value) -> Option<Value> {
+        if self.gamma == 0.0 {
+            return None;
+        }
+
+        let q = 1.0 - log_prob.exp();
+        if q.borrow().data < FOCUS_EPS {
+            Some(Value::new_const(FOCUS_EPS.powf(self.gamma)))
+        } else {
+            Some(q.pow(self.gamma))
+        }
+    }
+}
+
+impl Loss for FocalLoss {
+    fn sample_losses(&self, logits: &[Vec<Value>], ytrue: &[Vec<Value>]) -> Vec<Value> {
+        log_softmax(logits)
+            .iter()
+            .zip(ytrue)
+            .map(|(log_probs_i, ytrue_i)| {
+                self.options.check_outputs(log_probs_i.len());
+
+                // Classes without target mass do not contribute, nor take the focus' gradient.
+                -log_probs_i
+                    .iter()
+                    .zip(ytrue_i)
+                    .enumerate()
+                    .filter(|(_, (_, ytrue_j))| ytrue_j.borrow().data != 0.0)
+                    .map(|(j, (log_prob_j, ytrue_j))| {
+                        let ce = ytrue_j * log_prob_j * self.options.class_weight(j);
+                        match self.focus(log_prob_j) {
+                            Some(focus) => focus * ce,
+                            None => ce,
+                        }
+                    })
+                    .sum::<Value>()
+            })
+            .collect()
+    }
+
+    fn options(&self) -> &LossOptions {
+        &self.options
+    }
+
+    fn options_mut(&mut self) -> &mut LossOptions {
+        &mut self.options
+    }
+}
+
+impl ClassificationLoss for FocalLoss {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{loss::CrossEntropyWithLogitsLoss, utils::assert_gradients};
+
+    #[test]
+    fn gradients() {
+        let logits = Value::from_2d(&[&[1.0, 2.0, 0.5], &[0.3, -1.0, 2.0]]);
+        let inputs: Vec<Value> = logits.iter().flatten().cloned().collect();
+
+        for gamma in [0.5, 2.0] {
+            let loss = FocalLoss::new(gamma);
+            assert_gradients(|| loss.loss_from_labels(&logits, &[1, 0]), &inputs, 1e-5);
+        }
+    }
+
+    #[test]
+    fn zero_gamma_is_cross_entropy() {
+        let logits = Value::from_2d(&[&[1.0, 2.0, 0.5], &[0.3, -1.0, 2.0]]);
+        let focal = FocalLoss::new(0.0).loss_from_labels(&logits, &[1, 0]);
+        let ce = CrossEntropyWithLogitsLoss::new().loss_from_labels(&logits, &[1, 0]);
+        assert!((focal.borrow().data - ce.borrow().data).abs() < 1e-12);
+    }
+
+    #[test]
+    fn saturated_class() {
+        // p = 1 in f64 for the first class, so 1 - p = 0.
+        for gamma in [0.0, 0.5, 2.0] {
+            let logits = Value::from_2d(&[&[50.0, 0.0, 0.0]]);
+            let loss = FocalLoss::new(gamma).loss_from_labels(&logits, &[0]);
+            loss.backward();
+
+            assert!(loss.borrow().data.is_finite());
+            for logit in &logits[0] {
+                assert!(
+                    logit.borrow().grad.is_finite(),
+                    "NaN gradient for gamma {}",
+                    gamma
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,117 @@
+use crate::{
+    engine::Value,
+    loss::{Loss, LossOptions},
+};
+
+/** Gaussian negative log-likelihood loss
+For regression with a model that predicts the mean and the variance of each target, as
+`0.5 (ln(v) + (t - μ)² / v)`. Rows of predictions hold the means of the `n` targets followed by
+their variances, so they have `2n` values. Variances must be positive, through `softplus` or
+`exp` of the outputs for instance, and are clamped to `eps` from below. The loss of a sample is
+the mean over its targets.
+- `eps`: Smallest variance, for stability.*/
+#[derive(Debug)]
+pub struct GaussianNLLLoss {
+    pub eps: f64,
+    options: LossOptions,
+}
+
+impl GaussianNLLLoss {
+    pub fn new() -> GaussianNLLLoss {
+        GaussianNLLLoss {
+            eps: 1e-6,
+            options: LossOptions::default(),
+        }
+    }
+}
+
+impl Loss for GaussianNLLLoss {
+    fn sample_losses(&self, ypred: &[Vec<Value>], ytrue: &[Vec<Value>]) -> Vec<Value> {
+        ypred
+            .iter()
+            .zip(ytrue)
+            .map(|(ypred_i, ytrue_i)| {
+                assert!(
+                    ypred_i.len() == 2 * ytrue_i.len(),
+                    "Expected {} means and variances, found {} values",
+                    2 * ytrue_i.len(),
+                    ypred_i.len()
+                );
+
+                let (mean, var) = ypred_i.split_at(ytrue_i.len());
+                self.options.check_outputs(ytrue_i.len());
+
+                ytrue_i
+                    .iter()
+                    .zip(mean.iter().zip(var))
+                    .enumerate()
+                    .map(|(j, (ytrue_j, (mean_j, var_j)))| {
+                        // Clamped variances are constants, without gradient.
+                        let var_j = if var_j.borrow().data < self.eps {
+                            Value::new_const(self.eps)
+                        } else {
+                            var_j.clone()
+                        };
+                        0.5 * (var_j.ln() + (ytrue_j - mean_j).pow(2.0) / var_j)
+                            * self.options.class_weight(j)
+                    })
+                    .sum::<Value>()
+                    / ytrue_i.len() as f64
+            })
+            .collect()
+    }
+
+    fn options(&self) -> &LossOptions {
+        &self.options
+    }
+
+    fn options_mut(&mut self) -> &mut LossOptions {
+        &mut self.options
+    }
+}
+
+impl Default for GaussianNLLLoss {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::assert_gradients;
+
+    #[test]
+    fn gradients() {
+        // Rows of 2 means and 2 variances.
+        let ypred = Value::from_2d(&[&[0.5, -1.2, 0.8, 1.5], &[2.0, 0.3, 0.6, 2.5]]);
+        let ytrue = Value::from_2d(&[&[0.0, 1.0], &[-1.0, 0.1]]);
+        let inputs: Vec<Value> = ypred.iter().flatten().cloned().collect();
+
+        let loss = GaussianNLLLoss::new();
+        assert_gradients(|| loss.loss(&ypred, &ytrue), &inputs, 1e-5);
+
+        let nll = |t: f64, mean: f64, var: f64| 0.5 * (var.ln() + (t - mean).powi(2) / var);
+        let expected =
+            (nll(0.0, 0.5, 0.8) + nll(1.0, -1.2, 1.5) + nll(-1.0, 2.0, 0.6) + nll(0.1, 0.3, 2.5))
+                / 4.0;
+        assert!((loss.loss(&ypred, &ytrue).borrow().data - expected).abs() < 1e-12);
+    }
+
+    #[test]
+    fn clamped_variance() {
+        // The second variance is below `eps`, so it is replaced by `eps` and gets no gradient.
+        let ypred = Value::from_2d(&[&[0.5, -1.2, 0.8, 0.01]]);
+        let ytrue = Value::from_2d(&[&[0.0, 1.0]]);
+        let inputs: Vec<Value> = ypred.iter().flatten().cloned().collect();
+
+        let mut loss = GaussianNLLLoss::new();
+        loss.eps = 0.1;
+        assert_gradients(|| loss.loss(&ypred, &ytrue), &inputs, 1e-5);
+        assert_eq!(ypred[0][3].borrow().grad, 0.0);
+
+        let expected =
+            (0.5 * (0.8_f64.ln() + 0.25 / 0.8) + 0.5 * (0.1_f64.ln() + 2.2 * 2.2 / 0.1)) / 2.0;
+        assert!((loss.loss(&ypred, &ytrue).borrow().data - expected).abs() < 1e-9);
+    }
+}
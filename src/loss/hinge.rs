@@ -1,28 +1,152 @@
-use crate::engine::Value;
+use crate::{
+    engine::Value,
+    loss::{
+        labels::{binary_label_targets, one_hot},
+        ClassificationLoss, Loss, LossOptions,
+    },
+};
+
+/// Formulation of the multiclass hinge loss, with `s` the outputs and `y` the class of a sample.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MultiClassHinge {
+    /// Crammer and Singer, `max(0, 1 + max_{j≠y} sⱼ - s_y)`, the largest margin violation.
+    CrammerSinger,
+    /// Weston and Watkins, `Σ_{j≠y} max(0, 1 + sⱼ - s_y)`, the sum of the margin violations.
+    WestonWatkins,
+}
 
 /** Hinge loss
-For binary classification, when targets are 0 and 1.*/
+For binary classification, on the raw outputs of the model, when targets are -1 and 1. The loss
+of a sample is the mean over its outputs.
+
+With `multi_class`, for multiclass classification on the raw outputs, with one-hot targets or
+integer labels through `loss_from_labels`. The class of a sample is its largest target, and its
+loss is weighted by the weight of its class.*/
 #[derive(Debug)]
-pub struct HingeLoss;
+pub struct HingeLoss {
+    pub multi_class: Option<MultiClassHinge>,
+    options: LossOptions,
+}
 
 impl HingeLoss {
     pub fn new() -> HingeLoss {
-        HingeLoss
+        HingeLoss {
+            multi_class: None,
+            options: LossOptions::default(),
+        }
     }
 
-    pub fn loss(&self, ypred: &[Vec<Value>], ytrue: &[Vec<Value>]) -> Value {
+    pub fn multi_class(formulation: MultiClassHinge) -> HingeLoss {
+        HingeLoss {
+            multi_class: Some(formulation),
+            options: LossOptions::default(),
+        }
+    }
+
+    fn binary_losses(&self, ypred: &[Vec<Value>], ytrue: &[Vec<Value>]) -> Vec<Value> {
+        for ytrue_j in ytrue.iter().flatten() {
+            let t = ytrue_j.borrow().data;
+            assert!(
+                t == -1.0 || t == 1.0,
+                "Targets of the hinge loss must be -1 or 1, found {}",
+                t
+            );
+        }
+
         ypred
             .iter()
-            .zip(ytrue.iter())
+            .zip(ytrue)
             .map(|(ypred_i, ytrue_i)| {
-                ypred_i
-                    .iter()
-                    .zip(ytrue_i)
-                    .map(|(ypred_j, ytrue)| (1.0 - ytrue * ypred_j).relu())
-                    .sum::<Value>()
+                self.options
+                    .mean_over_outputs(ypred_i, ytrue_i, |ypred_j, ytrue_j| {
+                        (1.0 - ytrue_j * ypred_j).relu()
+                    })
             })
-            .sum::<Value>()
-            / (ypred.len() * ypred[0].len()) as f64
+            .collect()
+    }
+
+    fn multi_class_loss(
+        &self,
+        formulation: MultiClassHinge,
+        ypred_i: &[Value],
+        ytrue_i: &[Value],
+    ) -> Value {
+        assert!(
+            ypred_i.len() == ytrue_i.len() && ypred_i.len() > 1,
+            "Expected targets for each of the {} classes",
+            ypred_i.len()
+        );
+        self.options.check_outputs(ypred_i.len());
+
+        let class = argmax(ytrue_i);
+        let score = &ypred_i[class];
+        let others = ypred_i
+            .iter()
+            .enumerate()
+            .filter(|(j, _)| *j != class)
+            .map(|(_, ypred_j)| ypred_j);
+
+        let loss = match formulation {
+            MultiClassHinge::CrammerSinger => {
+                let rival = others
+                    .max_by(|a, b| a.borrow().data.total_cmp(&b.borrow().data))
+                    .expect("No other class");
+                (1.0 + rival - score).relu()
+            }
+            MultiClassHinge::WestonWatkins => {
+                others.map(|ypred_j| (1.0 + ypred_j - score).relu()).sum()
+            }
+        };
+        loss * self.options.class_weight(class)
+    }
+}
+
+impl Loss for HingeLoss {
+    fn sample_losses(&self, ypred: &[Vec<Value>], ytrue: &[Vec<Value>]) -> Vec<Value> {
+        match self.multi_class {
+            Some(formulation) => ypred
+                .iter()
+                .zip(ytrue)
+                .map(|(ypred_i, ytrue_i)| self.multi_class_loss(formulation, ypred_i, ytrue_i))
+                .collect(),
+            None => self.binary_losses(ypred, ytrue),
+        }
+    }
+
+    fn options(&self) -> &LossOptions {
+        &self.options
+    }
+
+    fn options_mut(&mut self) -> &mut LossOptions {
+        &mut self.options
+    }
+}
+
+impl ClassificationLoss for HingeLoss {
+    // Binary targets -1 and 1 would be moved off the margin, and the multiclass formulations only
+    // use the largest target of a sample, so smoothing would be ignored.
+    fn with_label_smoothing(self, smoothing: f64) -> Self {
+        assert!(
+            smoothing == 0.0,
+            "Label smoothing is not supported by the hinge loss"
+        );
+        self
+    }
+
+    // Binary outputs take labels 0 and 1 as targets -1 and 1.
+    fn label_targets(&self, labels: &[usize], n_outputs: usize) -> Vec<Vec<Value>> {
+        if self.multi_class.is_some() {
+            return one_hot(labels, n_outputs);
+        }
+
+        binary_label_targets(labels, n_outputs, 0.0)
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|t| Value::new_const(2.0 * t.borrow().data - 1.0))
+                    .collect()
+            })
+            .collect()
     }
 }
 
@@ -31,3 +155,12 @@ impl Default for HingeLoss {
         Self::new()
     }
 }
+
+fn argmax(values: &[Value]) -> usize {
+    values
+        .iter()
+        .enumerate()
+        .max_by(|(_, a), (_, b)| a.borrow().data.total_cmp(&b.borrow().data))
+        .map(|(j, _)| j)
+        .expect("Empty targets")
+}
@@ -0,0 +1,137 @@
+use crate::{
+    engine::Value,
+    loss::{l1::abs, Loss, LossOptions},
+};
+
+/** Huber loss
+For regression with outliers, quadratic for errors up to `delta` and linear above, as
+`0.5 d²` for `|d| ≤ delta` and `delta (|d| - 0.5 delta)` otherwise, with `d = t - y`. The loss of
+a sample is the mean over its outputs.
+- `delta`: Error at which the loss becomes linear.*/
+#[derive(Debug)]
+pub struct HuberLoss {
+    pub delta: f64,
+    options: LossOptions,
+}
+
+impl HuberLoss {
+    pub fn new(delta: f64) -> HuberLoss {
+        assert!(delta > 0.0, "delta must be positive");
+        HuberLoss {
+            delta,
+            options: LossOptions::default(),
+        }
+    }
+}
+
+impl Loss for HuberLoss {
+    fn sample_losses(&self, ypred: &[Vec<Value>], ytrue: &[Vec<Value>]) -> Vec<Value> {
+        ypred
+            .iter()
+            .zip(ytrue)
+            .map(|(ypred_i, ytrue_i)| {
+                self.options
+                    .mean_over_outputs(ypred_i, ytrue_i, |ypred_j, ytrue_j| {
+                        huber(&(ytrue_j - ypred_j), self.delta)
+                    })
+            })
+            .collect()
+    }
+
+    fn options(&self) -> &LossOptions {
+        &self.options
+    }
+
+    fn options_mut(&mut self) -> &mut LossOptions {
+        &mut self.options
+    }
+}
+
+/** Smooth L1 loss
+Huber loss divided by `beta`, as `0.5 d² / beta` for `|d| < beta` and `|d| - 0.5 beta` otherwise,
+so that the linear part has a slope of 1 like `L1Loss`. The loss of a sample is the mean over its
+outputs.
+- `beta`: Error at which the loss becomes linear.*/
+#[derive(Debug)]
+pub struct SmoothL1Loss {
+    pub beta: f64,
+    options: LossOptions,
+}
+
+impl SmoothL1Loss {
+    pub fn new(beta: f64) -> SmoothL1Loss {
+        assert!(beta > 0.0, "beta must be positive");
+        SmoothL1Loss {
+            beta,
+            options: LossOptions::default(),
+        }
+    }
+}
+
+impl Loss for SmoothL1Loss {
+    fn sample_losses(&self, ypred: &[Vec<Value>], ytrue: &[Vec<Value>]) -> Vec<Value> {
+        ypred
+            .iter()
+            .zip(ytrue)
+            .map(|(ypred_i, ytrue_i)| {
+                self.options
+                    .mean_over_outputs(ypred_i, ytrue_i, |ypred_j, ytrue_j| {
+                        huber(&(ytrue_j - ypred_j), self.beta) / self.beta
+                    })
+            })
+            .collect()
+    }
+
+    fn options(&self) -> &LossOptions {
+        &self.options
+    }
+
+    fn options_mut(&mut self) -> &mut LossOptions {
+        &mut self.options
+    }
+}
+
+fn huber(d: &Value, delta: f64) -> Value {
+    if d.borrow().data.abs() <= delta {
+        0.5 * d.pow(2.0)
+    } else {
+        delta * (abs(d) - 0.5 * delta)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::assert_gradients;
+
+    // Errors of 0.2 and 0.3 below `delta`, and 0.9 and 1.5 above.
+    fn rows() -> (Vec<Vec<Value>>, Vec<Vec<Value>>) {
+        let ypred = Value::from_2d(&[&[0.2, 1.5], &[-0.9, 0.3]]);
+        let ytrue = Value::from_2d(&[&[0.0, 0.0], &[0.0, 0.0]]);
+        (ypred, ytrue)
+    }
+
+    #[test]
+    fn huber_gradients() {
+        let (ypred, ytrue) = rows();
+        let inputs: Vec<Value> = ypred.iter().flatten().cloned().collect();
+
+        let loss = HuberLoss::new(0.5);
+        assert_gradients(|| loss.loss(&ypred, &ytrue), &inputs, 1e-5);
+
+        let expected = (0.5 * 0.04 + 0.5 * (1.5 - 0.25) + 0.5 * (0.9 - 0.25) + 0.5 * 0.09) / 4.0;
+        assert!((loss.loss(&ypred, &ytrue).borrow().data - expected).abs() < 1e-12);
+    }
+
+    #[test]
+    fn smooth_l1_gradients() {
+        let (ypred, ytrue) = rows();
+        let inputs: Vec<Value> = ypred.iter().flatten().cloned().collect();
+
+        let loss = SmoothL1Loss::new(0.5);
+        assert_gradients(|| loss.loss(&ypred, &ytrue), &inputs, 1e-5);
+
+        let expected = (0.04 + (1.5 - 0.25) + (0.9 - 0.25) + 0.09) / 4.0;
+        assert!((loss.loss(&ypred, &ytrue).borrow().data - expected).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,79 @@
+use crate::{
+    engine::Value,
+    loss::{Loss, LossOptions},
+};
+
+/** Mean Absolute Error loss (MAE)
+For regression, less sensitive to outliers than `MeanSquareErrorLoss`. The loss of a sample is
+the mean over its outputs of `|t - y|`.*/
+#[derive(Debug)]
+pub struct L1Loss {
+    options: LossOptions,
+}
+
+impl L1Loss {
+    pub fn new() -> L1Loss {
+        L1Loss {
+            options: LossOptions::default(),
+        }
+    }
+}
+
+impl Loss for L1Loss {
+    fn sample_losses(&self, ypred: &[Vec<Value>], ytrue: &[Vec<Value>]) -> Vec<Value> {
+        ypred
+            .iter()
+            .zip(ytrue)
+            .map(|(ypred_i, ytrue_i)| {
+                self.options
+                    .mean_over_outputs(ypred_i, ytrue_i, |ypred_j, ytrue_j| {
+                        abs(&(ytrue_j - ypred_j))
+                    })
+            })
+            .collect()
+    }
+
+    fn options(&self) -> &LossOptions {
+        &self.options
+    }
+
+    fn options_mut(&mut self) -> &mut LossOptions {
+        &mut self.options
+    }
+}
+
+impl Default for L1Loss {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// |x|, as x times its sign, with a gradient of 0 at 0.
+pub(crate) fn abs(x: &Value) -> Value {
+    let data = x.borrow().data;
+    let sign = if data > 0.0 {
+        1.0
+    } else if data < 0.0 {
+        -1.0
+    } else {
+        0.0
+    };
+    x * sign
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::assert_gradients;
+
+    #[test]
+    fn gradients() {
+        let ypred = Value::from_2d(&[&[0.5, -1.2], &[2.0, 0.3]]);
+        let ytrue = Value::from_2d(&[&[0.0, 1.0], &[-1.0, 0.1]]);
+        let inputs: Vec<Value> = ypred.iter().flatten().cloned().collect();
+
+        let loss = L1Loss::new();
+        assert_gradients(|| loss.loss(&ypred, &ytrue), &inputs, 1e-5);
+        assert!((loss.loss(&ypred, &ytrue).borrow().data - 5.9 / 4.0).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,138 @@
+use crate::engine::Value;
+
+/// One-hot rows of `n_classes` targets, for integer class labels.
+pub fn one_hot(labels: &[usize], n_classes: usize) -> Vec<Vec<Value>> {
+    smoothed_one_hot(labels, n_classes, 0.0)
+}
+
+// One-hot rows mixed with the uniform distribution, as (1 - smoothing) one_hot + smoothing / K.
+pub(crate) fn smoothed_one_hot(
+    labels: &[usize],
+    n_classes: usize,
+    smoothing: f64,
+) -> Vec<Vec<Value>> {
+    let off = smoothing / n_classes as f64;
+
+    labels
+        .iter()
+        .map(|&label| {
+            assert!(
+                label < n_classes,
+                "Label {} out of range for {} classes",
+                label,
+                n_classes
+            );
+            (0..n_classes)
+                .map(|j| {
+                    let on = if j == label { 1.0 - smoothing } else { 0.0 };
+                    Value::new_const(on + off)
+                })
+                .collect()
+        })
+        .collect()
+}
+
+// Targets of binary outputs. A single output takes labels 0 and 1 as its target, smoothed towards
+// 0.5 like 2 classes would be, and several outputs take one-hot rows.
+pub(crate) fn binary_label_targets(
+    labels: &[usize],
+    n_outputs: usize,
+    smoothing: f64,
+) -> Vec<Vec<Value>> {
+    if n_outputs != 1 {
+        return smoothed_one_hot(labels, n_outputs, smoothing);
+    }
+
+    labels
+        .iter()
+        .map(|&label| {
+            assert!(
+                label < 2,
+                "Label {} out of range for a binary output",
+                label
+            );
+            let target = label as f64 * (1.0 - smoothing) + smoothing / 2.0;
+            vec![Value::new_const(target)]
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::loss::{
+        BinaryCrossEntropyLoss, ClassificationLoss, CrossEntropyWithLogitsLoss, HingeLoss, Loss,
+        MultiClassHinge,
+    };
+
+    fn data(values: &[Value]) -> f64 {
+        values[0].borrow().data
+    }
+
+    #[test]
+    fn smoothed_one_hot_rows() {
+        let rows = smoothed_one_hot(&[2, 0], 4, 0.2);
+        let expected = [[0.05, 0.05, 0.85, 0.05], [0.85, 0.05, 0.05, 0.05]];
+
+        for (row, expected) in rows.iter().zip(expected) {
+            for (t, expected) in row.iter().zip(expected) {
+                assert!((t.borrow().data - expected).abs() < 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn labels_match_one_hot_targets() {
+        let logits = Value::from_2d(&[&[1.0, 2.0, 0.5], &[0.3, -1.0, 2.0]]);
+        let loss = CrossEntropyWithLogitsLoss::new();
+        assert_eq!(
+            loss.loss_from_labels(&logits, &[1, 0]).borrow().data,
+            loss.loss(&logits, &one_hot(&[1, 0], 3)).borrow().data
+        );
+
+        let hinge = HingeLoss::multi_class(MultiClassHinge::WestonWatkins);
+        assert_eq!(
+            hinge.loss_from_labels(&logits, &[1, 0]).borrow().data,
+            hinge.loss(&logits, &one_hot(&[1, 0], 3)).borrow().data
+        );
+    }
+
+    #[test]
+    fn binary_labels() {
+        let ypred = Value::from_2d(&[&[0.8], &[-0.4]]);
+
+        // Labels 0 and 1 are targets -1 and 1 for the hinge loss.
+        let hinge = HingeLoss::new();
+        let ytrue = Value::from_2d(&[&[1.0], &[-1.0]]);
+        assert_eq!(
+            data(&hinge.losses_from_labels(&ypred, &[1, 0])),
+            data(&hinge.losses(&ypred, &ytrue))
+        );
+
+        let probs = Value::from_2d(&[&[0.8], &[0.3]]);
+        let bce = BinaryCrossEntropyLoss::new().with_label_smoothing(0.2);
+        let ytrue = Value::from_2d(&[&[0.9], &[0.1]]);
+        assert_eq!(
+            data(&bce.losses_from_labels(&probs, &[1, 0])),
+            data(&bce.losses(&probs, &ytrue))
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Label smoothing is not supported by the hinge loss")]
+    fn binary_hinge_rejects_label_smoothing() {
+        HingeLoss::new().with_label_smoothing(0.1);
+    }
+
+    #[test]
+    #[should_panic(expected = "Label smoothing is not supported by the hinge loss")]
+    fn multi_class_hinge_rejects_label_smoothing() {
+        HingeLoss::multi_class(MultiClassHinge::CrammerSinger).with_label_smoothing(0.1);
+    }
+
+    #[test]
+    #[should_panic(expected = "Label 3 out of range for 3 classes")]
+    fn label_out_of_range() {
+        one_hot(&[0, 3], 3);
+    }
+}
@@ -0,0 +1,76 @@
+use crate::{
+    engine::Value,
+    loss::{l1::abs, Loss, LossOptions},
+};
+use std::f64::consts::LN_2;
+
+/** Log-cosh loss
+For regression, close to `0.5 d²` for small errors and to `|d| - ln 2` for large ones, with
+`d = t - y`, and smooth everywhere. Computed as `|d| + softplus(-2|d|) - ln 2`, which does not
+overflow for large errors. The loss of a sample is the mean over its outputs.*/
+#[derive(Debug)]
+pub struct LogCoshLoss {
+    options: LossOptions,
+}
+
+impl LogCoshLoss {
+    pub fn new() -> LogCoshLoss {
+        LogCoshLoss {
+            options: LossOptions::default(),
+        }
+    }
+}
+
+impl Loss for LogCoshLoss {
+    fn sample_losses(&self, ypred: &[Vec<Value>], ytrue: &[Vec<Value>]) -> Vec<Value> {
+        ypred
+            .iter()
+            .zip(ytrue)
+            .map(|(ypred_i, ytrue_i)| {
+                self.options
+                    .mean_over_outputs(ypred_i, ytrue_i, |ypred_j, ytrue_j| {
+                        let d = abs(&(ytrue_j - ypred_j));
+                        &d + (-2.0 * &d).softplus() - LN_2
+                    })
+            })
+            .collect()
+    }
+
+    fn options(&self) -> &LossOptions {
+        &self.options
+    }
+
+    fn options_mut(&mut self) -> &mut LossOptions {
+        &mut self.options
+    }
+}
+
+impl Default for LogCoshLoss {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::assert_gradients;
+
+    #[test]
+    fn gradients() {
+        // An error of 800 would overflow cosh.
+        let ypred = Value::from_2d(&[&[0.5, -1.2], &[800.0, 0.3]]);
+        let ytrue = Value::from_2d(&[&[0.0, 1.0], &[0.0, 0.1]]);
+        let inputs: Vec<Value> = ypred.iter().flatten().cloned().collect();
+
+        let loss = LogCoshLoss::new();
+        assert_gradients(|| loss.loss(&ypred, &ytrue), &inputs, 1e-5);
+
+        let expected = [0.5_f64, 2.2, 0.2]
+            .iter()
+            .map(|d| d.cosh().ln())
+            .sum::<f64>();
+        let expected = (expected + 800.0 - LN_2) / 4.0;
+        assert!((loss.loss(&ypred, &ytrue).borrow().data - expected).abs() < 1e-9);
+    }
+}
@@ -1,9 +1,31 @@
 mod bce;
+mod bce_logits;
 mod ce;
+mod ce_logits;
+mod criterion;
+mod focal;
+mod gaussian_nll;
 mod hinge;
+mod huber;
+mod l1;
+mod labels;
+mod log_cosh;
 mod mse;
+mod nll;
+mod quantile;
 
 pub use bce::*;
+pub use bce_logits::*;
 pub use ce::*;
+pub use ce_logits::*;
+pub use criterion::*;
+pub use focal::*;
+pub use gaussian_nll::*;
 pub use hinge::*;
+pub use huber::*;
+pub use l1::*;
+pub use labels::*;
+pub use log_cosh::*;
 pub use mse::*;
+pub use nll::*;
+pub use quantile::*;
@@ -1,29 +1,44 @@
-use crate::engine::Value;
+use crate::{
+    engine::Value,
+    loss::{Loss, LossOptions},
+};
 
 /** Mean Square Error loss (MSE)
-To use Root Mean Square Error loss (RMSE), use `mse.loss().pow(0.5)`.*/
+The loss of a sample is the mean over its outputs. To use Root Mean Square Error loss (RMSE),
+use `mse.loss().pow(0.5)`.*/
 #[derive(Debug)]
-pub struct MeanSquareErrorLoss {}
+pub struct MeanSquareErrorLoss {
+    options: LossOptions,
+}
 
 impl MeanSquareErrorLoss {
     pub fn new() -> MeanSquareErrorLoss {
-        MeanSquareErrorLoss {}
+        MeanSquareErrorLoss {
+            options: LossOptions::default(),
+        }
     }
+}
 
-    pub fn loss(&self, ypred: &[Vec<Value>], ytrue: &[Vec<Value>]) -> Value {
+impl Loss for MeanSquareErrorLoss {
+    fn sample_losses(&self, ypred: &[Vec<Value>], ytrue: &[Vec<Value>]) -> Vec<Value> {
         ypred
             .iter()
             .zip(ytrue)
             .map(|(ypred_i, ytrue_i)| {
-                ypred_i
-                    .iter()
-                    .zip(ytrue_i)
-                    .map(|(ypred_j, ytrue_j)| (ytrue_j - ypred_j).pow(2.0))
-                    .sum::<Value>()
-                    / ypred_i.len() as f64
+                self.options
+                    .mean_over_outputs(ypred_i, ytrue_i, |ypred_j, ytrue_j| {
+                        (ytrue_j - ypred_j).pow(2.0)
+                    })
             })
-            .sum::<Value>()
-            / ypred.len() as f64
+            .collect()
+    }
+
+    fn options(&self) -> &LossOptions {
+        &self.options
+    }
+
+    fn options_mut(&mut self) -> &mut LossOptions {
+        &mut self.options
     }
 }
 
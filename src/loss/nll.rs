@@ -0,0 +1,58 @@
+use crate::{
+    engine::Value,
+    loss::{ClassificationLoss, Loss, LossOptions},
+};
+
+/** Negative Log-Likelihood loss (NLL)
+For multiclass classification, on log-probabilities such as `log_softmax` of the outputs. The
+loss of a sample is the sum over the classes of `-wⱼ tⱼ log(pⱼ)`, so with integer labels through
+`loss_from_labels` it is the negative log-probability of the label.*/
+#[derive(Debug)]
+pub struct NLLLoss {
+    options: LossOptions,
+}
+
+impl NLLLoss {
+    pub fn new() -> NLLLoss {
+        NLLLoss {
+            options: LossOptions::default(),
+        }
+    }
+}
+
+impl Loss for NLLLoss {
+    fn sample_losses(&self, log_probs: &[Vec<Value>], ytrue: &[Vec<Value>]) -> Vec<Value> {
+        log_probs
+            .iter()
+            .zip(ytrue)
+            .map(|(log_probs_i, ytrue_i)| {
+                self.options.check_outputs(log_probs_i.len());
+
+                -log_probs_i
+                    .iter()
+                    .zip(ytrue_i)
+                    .enumerate()
+                    .map(|(j, (log_prob_j, ytrue_j))| {
+                        ytrue_j * log_prob_j * self.options.class_weight(j)
+                    })
+                    .sum::<Value>()
+            })
+            .collect()
+    }
+
+    fn options(&self) -> &LossOptions {
+        &self.options
+    }
+
+    fn options_mut(&mut self) -> &mut LossOptions {
+        &mut self.options
+    }
+}
+
+impl ClassificationLoss for NLLLoss {}
+
+impl Default for NLLLoss {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,76 @@
+use crate::{
+    engine::Value,
+    loss::{Loss, LossOptions},
+};
+
+/** Quantile loss, or pinball loss
+For regression of the `quantile` of the targets, as `quantile d` for `d ≥ 0` and
+`(quantile - 1) d` otherwise, with `d = t - y`. A quantile of 0.5 is half of `L1Loss`, the
+median. The loss of a sample is the mean over its outputs.
+- `quantile`: Quantile to predict, in (0, 1).*/
+#[derive(Debug)]
+pub struct QuantileLoss {
+    pub quantile: f64,
+    options: LossOptions,
+}
+
+impl QuantileLoss {
+    pub fn new(quantile: f64) -> QuantileLoss {
+        assert!(
+            quantile > 0.0 && quantile < 1.0,
+            "quantile must be in (0, 1)"
+        );
+        QuantileLoss {
+            quantile,
+            options: LossOptions::default(),
+        }
+    }
+}
+
+impl Loss for QuantileLoss {
+    fn sample_losses(&self, ypred: &[Vec<Value>], ytrue: &[Vec<Value>]) -> Vec<Value> {
+        ypred
+            .iter()
+            .zip(ytrue)
+            .map(|(ypred_i, ytrue_i)| {
+                self.options
+                    .mean_over_outputs(ypred_i, ytrue_i, |ypred_j, ytrue_j| {
+                        let d = ytrue_j - ypred_j;
+                        if d.borrow().data >= 0.0 {
+                            self.quantile * &d
+                        } else {
+                            (self.quantile - 1.0) * &d
+                        }
+                    })
+            })
+            .collect()
+    }
+
+    fn options(&self) -> &LossOptions {
+        &self.options
+    }
+
+    fn options_mut(&mut self) -> &mut LossOptions {
+        &mut self.options
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::assert_gradients;
+
+    #[test]
+    fn gradients() {
+        // Targets above and below the predictions.
+        let ypred = Value::from_2d(&[&[0.5, -1.2], &[2.0, 0.3]]);
+        let ytrue = Value::from_2d(&[&[0.0, 1.0], &[-1.0, 0.4]]);
+        let inputs: Vec<Value> = ypred.iter().flatten().cloned().collect();
+
+        let loss = QuantileLoss::new(0.9);
+        assert_gradients(|| loss.loss(&ypred, &ytrue), &inputs, 1e-5);
+
+        let expected = (0.1 * 0.5 + 0.9 * 2.2 + 0.1 * 3.0 + 0.9 * 0.1) / 4.0;
+        assert!((loss.loss(&ypred, &ytrue).borrow().data - expected).abs() < 1e-12);
+    }
+}
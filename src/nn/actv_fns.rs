@@ -1,17 +1,145 @@
-use crate::engine::Value;
+use crate::engine::{ActvFn, Op, Value};
+use crate::nn::Module;
+use std::fmt;
 
 pub fn softmax(y: &[Vec<Value>]) -> Vec<Vec<Value>> {
-    y.iter()
-        .map(|y_i| {
-            let exp_yi = y_i.iter().map(|y_ij| y_ij.exp());
-            let exp_sum = exp_yi.clone().sum::<Value>();
-            exp_yi.map(|y_ij| y_ij / &exp_sum).collect()
-        })
-        .collect()
+    Softmax.forward(y)
+}
+
+pub fn log_softmax(y: &[Vec<Value>]) -> Vec<Vec<Value>> {
+    LogSoftmax.forward(y)
 }
 
 pub fn sigmoid(y: &[Vec<Value>]) -> Vec<Vec<Value>> {
-    y.iter()
-        .map(|y_i| y_i.iter().map(|y_ij| y_ij.sigmoid()).collect())
-        .collect()
+    Activation::new(ActvFn::Sigmoid).forward(y)
+}
+
+/// Activation function of a whole layer, either element-wise or softmax over the outputs.
+#[derive(Debug, Clone, Copy)]
+pub enum LayerActvFn {
+    ActvFn(ActvFn),
+    Softmax,
+}
+
+impl From<ActvFn> for LayerActvFn {
+    fn from(actv_fn: ActvFn) -> LayerActvFn {
+        LayerActvFn::ActvFn(actv_fn)
+    }
+}
+
+/// Element-wise activation function, as a standalone module. `PReLU` is only supported by a
+/// `Neuron`, which holds its learnable slope.
+#[derive(Debug)]
+pub struct Activation {
+    actv_fn: ActvFn,
+}
+
+impl Activation {
+    pub fn new(actv_fn: ActvFn) -> Activation {
+        assert!(
+            !matches!(actv_fn, ActvFn::PReLU(_)),
+            "PReLU needs a learnable slope, use prelu or a Neuron"
+        );
+        Activation { actv_fn }
+    }
+}
+
+impl Module for Activation {
+    fn forw(&self, x: &[Value]) -> Vec<Value> {
+        x.iter().map(|x_i| x_i.activate(self.actv_fn)).collect()
+    }
+
+    fn parameters(&self) -> Vec<Value> {
+        vec![]
+    }
+}
+
+impl fmt::Display for Activation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", Op::ActvFn(self.actv_fn))
+    }
+}
+
+/// Softmax over each row, as a standalone module.
+#[derive(Debug)]
+pub struct Softmax;
+
+impl Module for Softmax {
+    fn forw(&self, x: &[Value]) -> Vec<Value> {
+        let max = max_data(x);
+        let exp_x: Vec<Value> = x.iter().map(|x_i| (x_i - max).exp()).collect();
+        let exp_sum = exp_x.iter().cloned().sum::<Value>();
+        exp_x.iter().map(|exp_i| exp_i / &exp_sum).collect()
+    }
+
+    fn parameters(&self) -> Vec<Value> {
+        vec![]
+    }
+}
+
+impl fmt::Display for Softmax {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "softmax")
+    }
+}
+
+/// Log of the softmax over each row, `x - ln(Σ exp(x))`, as a standalone module.
+#[derive(Debug)]
+pub struct LogSoftmax;
+
+impl Module for LogSoftmax {
+    fn forw(&self, x: &[Value]) -> Vec<Value> {
+        let max = max_data(x);
+        let shifted: Vec<Value> = x.iter().map(|x_i| x_i - max).collect();
+        let log_sum_exp = shifted.iter().map(|s_i| s_i.exp()).sum::<Value>().ln();
+        shifted.iter().map(|s_i| s_i - &log_sum_exp).collect()
+    }
+
+    fn parameters(&self) -> Vec<Value> {
+        vec![]
+    }
+}
+
+impl fmt::Display for LogSoftmax {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "log_softmax")
+    }
+}
+
+// Shifting a row by its maximum leaves softmax unchanged, but keeps exp from overflowing.
+fn max_data(x: &[Value]) -> f64 {
+    x.iter()
+        .map(|x_i| x_i.borrow().data)
+        .fold(f64::NEG_INFINITY, f64::max)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nn::Neuron;
+
+    #[test]
+    #[should_panic(expected = "PReLU needs a learnable slope, use prelu or a Neuron")]
+    fn activation_rejects_prelu() {
+        Activation::new(ActvFn::PReLU(0.25));
+    }
+
+    #[test]
+    #[should_panic(expected = "PReLU needs a learnable slope, use prelu or a Neuron")]
+    fn activate_rejects_prelu() {
+        Value::new(-1.0).activate(ActvFn::PReLU(0.25));
+    }
+
+    #[test]
+    fn prelu_neuron_learns_and_prints_slope() {
+        let neuron = Neuron::from_data(&[1.0, 2.0], 0.0, Some(ActvFn::PReLU(0.25)));
+        assert_eq!(neuron.to_string(), "PReLU(0.25)(2)");
+
+        let y = neuron.forw(&Value::new_1d(&[1.0, -2.0]))[0].clone();
+        assert_eq!(y.borrow().data, -0.75);
+
+        y.backward();
+        let slope = neuron.slope.as_ref().expect("PReLU neuron without a slope");
+        assert_eq!(slope.borrow().grad, -3.0);
+    }
 }
@@ -0,0 +1,271 @@
+use crate::engine::{ActvFn, Value};
+use crate::nn::{Init, Layer, LayerNorm, Module, Softmax};
+use std::fmt;
+
+/**
+Scaled dot-product attention, `softmax(q kᵀ / √d) v`, over sequences of rows.
+- `q`: Queries, one row per position.
+- `k`, `v`: Keys and values, one row per position.
+- `causal`: Each query only attends to the keys at or before its position.
+*/
+pub fn scaled_dot_product_attention(
+    q: &[Vec<Value>],
+    k: &[Vec<Value>],
+    v: &[Vec<Value>],
+    causal: bool,
+) -> Vec<Vec<Value>> {
+    let scale = 1.0 / (q.first().map_or(1, Vec::len) as f64).sqrt();
+
+    q.iter()
+        .enumerate()
+        .map(|(i, q_i)| {
+            let visible = if causal { i + 1 } else { k.len() };
+            let scores: Vec<Value> = k[..visible]
+                .iter()
+                .map(|k_j| q_i.iter().zip(k_j).map(|(a, b)| a * b).sum::<Value>() * scale)
+                .collect();
+            let weights = Softmax.forw(&scores);
+
+            (0..v[0].len())
+                .map(|c| {
+                    weights
+                        .iter()
+                        .zip(v)
+                        .map(|(w, v_j)| w * &v_j[c])
+                        .sum::<Value>()
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/**
+Multi-head self-attention, with query, key, value and output projections.
+The `embed_dim` features of each position are split into `num_heads` heads, which attend
+separately and are concatenated before the output projection.
+*/
+pub struct MultiHeadAttention {
+    q_proj: Layer,
+    k_proj: Layer,
+    v_proj: Layer,
+    o_proj: Layer,
+    embed_dim: usize,
+    num_heads: usize,
+    causal: bool,
+}
+
+impl MultiHeadAttention {
+    pub fn new(embed_dim: u32, num_heads: u32, causal: bool) -> MultiHeadAttention {
+        assert!(
+            embed_dim.is_multiple_of(num_heads),
+            "embed_dim must be divisible by num_heads"
+        );
+
+        let proj = || {
+            Layer::with_init(
+                embed_dim,
+                embed_dim,
+                None,
+                &Init::XavierUniform,
+                &Init::Zeros,
+            )
+        };
+
+        MultiHeadAttention {
+            q_proj: proj(),
+            k_proj: proj(),
+            v_proj: proj(),
+            o_proj: proj(),
+            embed_dim: embed_dim as usize,
+            num_heads: num_heads as usize,
+            causal,
+        }
+    }
+
+    /// Self-attention over a sequence, one row of `embed_dim` features per position.
+    pub fn forward_sequence(&self, x: &[Vec<Value>]) -> Vec<Vec<Value>> {
+        let (q, k, v) = (
+            self.q_proj.forward(x),
+            self.k_proj.forward(x),
+            self.v_proj.forward(x),
+        );
+        let head_dim = self.embed_dim / self.num_heads;
+        let head = |rows: &[Vec<Value>], h: usize| -> Vec<Vec<Value>> {
+            rows.iter()
+                .map(|row| row[h * head_dim..(h + 1) * head_dim].to_vec())
+                .collect()
+        };
+
+        let mut out = vec![Vec::with_capacity(self.embed_dim); x.len()];
+        for h in 0..self.num_heads {
+            let attn =
+                scaled_dot_product_attention(&head(&q, h), &head(&k, h), &head(&v, h), self.causal);
+            for (out_i, attn_i) in out.iter_mut().zip(attn) {
+                out_i.extend(attn_i);
+            }
+        }
+
+        self.o_proj.forward(&out)
+    }
+}
+
+/**
+Pre-norm transformer block, as in GPT-2.
+- `x = x + attention(layer_norm(x))`
+- `x = x + feed_forward(layer_norm(x))`, with a ReLU hidden layer of `ff_dim` features.
+*/
+pub struct TransformerBlock {
+    ln1: LayerNorm,
+    attn: MultiHeadAttention,
+    ln2: LayerNorm,
+    ff1: Layer,
+    ff2: Layer,
+    ff_dim: usize,
+}
+
+impl TransformerBlock {
+    pub fn new(embed_dim: u32, num_heads: u32, ff_dim: u32, causal: bool) -> TransformerBlock {
+        TransformerBlock {
+            ln1: LayerNorm::new(embed_dim, 1e-5),
+            attn: MultiHeadAttention::new(embed_dim, num_heads, causal),
+            ln2: LayerNorm::new(embed_dim, 1e-5),
+            ff1: Layer::with_init(
+                embed_dim,
+                ff_dim,
+                Some(ActvFn::ReLU),
+                &Init::HeUniform,
+                &Init::Zeros,
+            ),
+            ff2: Layer::with_init(ff_dim, embed_dim, None, &Init::XavierUniform, &Init::Zeros),
+            ff_dim: ff_dim as usize,
+        }
+    }
+
+    /// Block applied to a sequence, one row of `embed_dim` features per position.
+    pub fn forward_sequence(&self, x: &[Vec<Value>]) -> Vec<Vec<Value>> {
+        let attn = self.attn.forward_sequence(&self.ln1.forward(x));
+        let x: Vec<Vec<Value>> = x
+            .iter()
+            .zip(attn)
+            .map(|(x_i, a_i)| add(x_i, &a_i))
+            .collect();
+
+        let ff = self.ff2.forward(&self.ff1.forward(&self.ln2.forward(&x)));
+        x.iter().zip(ff).map(|(x_i, f_i)| add(x_i, &f_i)).collect()
+    }
+}
+
+fn add(a: &[Value], b: &[Value]) -> Vec<Value> {
+    a.iter().zip(b).map(|(a_i, b_i)| a_i + b_i).collect()
+}
+
+// Module impls take a sequence flattened position by position.
+
+fn unflatten(x: &[Value], embed_dim: usize) -> Vec<Vec<Value>> {
+    assert!(
+        x.len().is_multiple_of(embed_dim),
+        "Mismatching input size for attention"
+    );
+    x.chunks(embed_dim).map(|row| row.to_vec()).collect()
+}
+
+fn prefixed(prefix: &str, module: &dyn Module) -> Vec<(String, Value)> {
+    module
+        .named_parameters()
+        .into_iter()
+        .map(|(name, p)| (format!("{}.{}", prefix, name), p))
+        .collect()
+}
+
+impl Module for MultiHeadAttention {
+    fn forw(&self, x: &[Value]) -> Vec<Value> {
+        self.forward_sequence(&unflatten(x, self.embed_dim))
+            .concat()
+    }
+
+    fn parameters(&self) -> Vec<Value> {
+        [&self.q_proj, &self.k_proj, &self.v_proj, &self.o_proj]
+            .iter()
+            .flat_map(|proj| proj.parameters())
+            .collect()
+    }
+
+    fn named_parameters(&self) -> Vec<(String, Value)> {
+        [
+            ("q_proj", &self.q_proj),
+            ("k_proj", &self.k_proj),
+            ("v_proj", &self.v_proj),
+            ("o_proj", &self.o_proj),
+        ]
+        .iter()
+        .flat_map(|(prefix, proj)| prefixed(prefix, *proj))
+        .collect()
+    }
+}
+
+impl Module for TransformerBlock {
+    fn forw(&self, x: &[Value]) -> Vec<Value> {
+        let embed_dim = self.attn.embed_dim;
+        self.forward_sequence(&unflatten(x, embed_dim)).concat()
+    }
+
+    fn parameters(&self) -> Vec<Value> {
+        let modules: [&dyn Module; 5] = [&self.ln1, &self.attn, &self.ln2, &self.ff1, &self.ff2];
+        modules.iter().flat_map(|m| m.parameters()).collect()
+    }
+
+    fn named_parameters(&self) -> Vec<(String, Value)> {
+        let modules: [(&str, &dyn Module); 5] = [
+            ("ln1", &self.ln1),
+            ("attn", &self.attn),
+            ("ln2", &self.ln2),
+            ("ff1", &self.ff1),
+            ("ff2", &self.ff2),
+        ];
+        modules
+            .iter()
+            .flat_map(|(prefix, m)| prefixed(prefix, *m))
+            .collect()
+    }
+}
+
+impl fmt::Display for MultiHeadAttention {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}attention({}, {} heads)",
+            if self.causal { "causal_" } else { "" },
+            self.embed_dim,
+            self.num_heads
+        )
+    }
+}
+
+impl fmt::Debug for MultiHeadAttention {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MultiHeadAttention")
+            .field("embed_dim", &self.embed_dim)
+            .field("num_heads", &self.num_heads)
+            .field("causal", &self.causal)
+            .finish()
+    }
+}
+
+impl fmt::Display for TransformerBlock {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "transformer_block({}, feed_forward {})",
+            self.attn, self.ff_dim
+        )
+    }
+}
+
+impl fmt::Debug for TransformerBlock {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TransformerBlock")
+            .field("attn", &self.attn)
+            .field("ff_dim", &self.ff_dim)
+            .finish()
+    }
+}
@@ -0,0 +1,383 @@
+use crate::engine::Value;
+use crate::nn::{Init, Module};
+use crate::utils::with_rng;
+use std::fmt;
+
+/**
+2d convolution over an input of shape `channels × height × width`, flattened in that order.
+- `kernel_size`: Height and width of the square kernel.
+- `stride`: Step between kernel positions.
+- `padding`: Zero padding added to each side of the input.
+- `input_size`: Height and width of the input.
+
+Weights are drawn from `Init::HeUniform` and biases are zero.
+*/
+pub struct Conv2d {
+    weights: Vec<Vec<Value>>,
+    biases: Vec<Value>,
+    in_channels: usize,
+    kernel_size: usize,
+    stride: usize,
+    padding: usize,
+    input_size: (usize, usize),
+}
+
+impl Conv2d {
+    pub fn new(
+        in_channels: u32,
+        out_channels: u32,
+        kernel_size: u32,
+        stride: u32,
+        padding: u32,
+        input_size: (u32, u32),
+    ) -> Conv2d {
+        let (in_c, out_c, k) = (
+            in_channels as usize,
+            out_channels as usize,
+            kernel_size as usize,
+        );
+        assert!(k > 0, "Kernel size must be positive");
+        assert!(stride > 0, "Stride must be positive");
+        let (fan_in, fan_out) = (in_c * k * k, out_c * k * k);
+        let weights = with_rng(|rng| Init::HeUniform.sample(out_c, fan_in, fan_in, fan_out, rng));
+
+        let conv = Conv2d {
+            weights: weights.iter().map(|w| Value::new_1d(w)).collect(),
+            biases: Value::new_1d(&vec![0.0; out_c]),
+            in_channels: in_c,
+            kernel_size: k,
+            stride: stride as usize,
+            padding: padding as usize,
+            input_size: (input_size.0 as usize, input_size.1 as usize),
+        };
+        let (_, h, w) = conv.output_shape();
+        assert!(h > 0 && w > 0, "Kernel is larger than the padded input");
+
+        conv
+    }
+
+    /// Shape of the output, `(channels, height, width)`.
+    pub fn output_shape(&self) -> (usize, usize, usize) {
+        let (h, w) = self.input_size;
+        let (k, s, p) = (self.kernel_size, self.stride, self.padding);
+        let out_len = |len: usize| {
+            if len + 2 * p < k {
+                0
+            } else {
+                (len + 2 * p - k) / s + 1
+            }
+        };
+        (self.weights.len(), out_len(h), out_len(w))
+    }
+}
+
+impl Module for Conv2d {
+    fn forw(&self, x: &[Value]) -> Vec<Value> {
+        let (h, w) = self.input_size;
+        let (k, s, p) = (self.kernel_size, self.stride, self.padding);
+        let (_, out_h, out_w) = self.output_shape();
+        assert!(
+            x.len() == self.in_channels * h * w,
+            "Mismatching input size for Conv2d"
+        );
+
+        let mut out = Vec::with_capacity(self.weights.len() * out_h * out_w);
+        for (kernel, bias) in self.weights.iter().zip(&self.biases) {
+            for oy in 0..out_h {
+                for ox in 0..out_w {
+                    let mut terms = vec![];
+                    for c in 0..self.in_channels {
+                        for ky in 0..k {
+                            for kx in 0..k {
+                                // Position in the unpadded input, skipped when in the padding.
+                                let (iy, ix) = (oy * s + ky, ox * s + kx);
+                                if iy < p || ix < p || iy - p >= h || ix - p >= w {
+                                    continue;
+                                }
+                                let w_i = &kernel[(c * k + ky) * k + kx];
+                                terms.push(w_i * &x[(c * h + iy - p) * w + ix - p]);
+                            }
+                        }
+                    }
+                    out.push(terms.into_iter().sum::<Value>() + bias);
+                }
+            }
+        }
+
+        out
+    }
+
+    fn parameters(&self) -> Vec<Value> {
+        self.weights
+            .iter()
+            .flatten()
+            .chain(&self.biases)
+            .cloned()
+            .collect()
+    }
+
+    fn named_parameters(&self) -> Vec<(String, Value)> {
+        named_kernels(&self.weights, &self.biases)
+    }
+}
+
+impl fmt::Display for Conv2d {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (h, w) = self.input_size;
+        let (c, out_h, out_w) = self.output_shape();
+        write!(
+            f,
+            "conv2d({}×{}×{}, kernel {}, stride {}, padding {}) → {}×{}×{}",
+            self.in_channels, h, w, self.kernel_size, self.stride, self.padding, c, out_h, out_w
+        )
+    }
+}
+
+impl fmt::Debug for Conv2d {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Conv2d")
+            .field("in_channels", &self.in_channels)
+            .field("out_channels", &self.weights.len())
+            .field("kernel_size", &self.kernel_size)
+            .field("stride", &self.stride)
+            .field("padding", &self.padding)
+            .field("input_size", &self.input_size)
+            .finish()
+    }
+}
+
+/**
+1d convolution over an input of shape `channels × length`, flattened in that order.
+- `kernel_size`: Length of the kernel.
+- `stride`: Step between kernel positions.
+- `padding`: Zero padding added to each end of the input.
+- `dilation`: Spacing between the kernel taps.
+- `input_len`: Length of the input.
+
+Weights are drawn from `Init::HeUniform` and biases are zero.
+*/
+pub struct Conv1d {
+    weights: Vec<Vec<Value>>,
+    biases: Vec<Value>,
+    in_channels: usize,
+    kernel_size: usize,
+    stride: usize,
+    padding: usize,
+    dilation: usize,
+    input_len: usize,
+}
+
+impl Conv1d {
+    pub fn new(
+        in_channels: u32,
+        out_channels: u32,
+        kernel_size: u32,
+        stride: u32,
+        padding: u32,
+        dilation: u32,
+        input_len: u32,
+    ) -> Conv1d {
+        let (in_c, out_c, k) = (
+            in_channels as usize,
+            out_channels as usize,
+            kernel_size as usize,
+        );
+        assert!(k > 0, "Kernel size must be positive");
+        assert!(stride > 0, "Stride must be positive");
+        assert!(dilation > 0, "Dilation must be positive");
+        let (fan_in, fan_out) = (in_c * k, out_c * k);
+        let weights = with_rng(|rng| Init::HeUniform.sample(out_c, fan_in, fan_in, fan_out, rng));
+
+        let conv = Conv1d {
+            weights: weights.iter().map(|w| Value::new_1d(w)).collect(),
+            biases: Value::new_1d(&vec![0.0; out_c]),
+            in_channels: in_c,
+            kernel_size: k,
+            stride: stride as usize,
+            padding: padding as usize,
+            dilation: dilation as usize,
+            input_len: input_len as usize,
+        };
+        let (_, len) = conv.output_shape();
+        assert!(len > 0, "Kernel is larger than the padded input");
+
+        conv
+    }
+
+    /// Shape of the output, `(channels, length)`.
+    pub fn output_shape(&self) -> (usize, usize) {
+        let span = self.dilation * (self.kernel_size - 1) + 1;
+        let padded = self.input_len + 2 * self.padding;
+        let len = if padded < span {
+            0
+        } else {
+            (padded - span) / self.stride + 1
+        };
+        (self.weights.len(), len)
+    }
+}
+
+impl Module for Conv1d {
+    fn forw(&self, x: &[Value]) -> Vec<Value> {
+        let (k, s, p, d) = (self.kernel_size, self.stride, self.padding, self.dilation);
+        let (l, (_, out_l)) = (self.input_len, self.output_shape());
+        assert!(
+            x.len() == self.in_channels * l,
+            "Mismatching input size for Conv1d"
+        );
+
+        let mut out = Vec::with_capacity(self.weights.len() * out_l);
+        for (kernel, bias) in self.weights.iter().zip(&self.biases) {
+            for o in 0..out_l {
+                let mut terms = vec![];
+                for c in 0..self.in_channels {
+                    for t in 0..k {
+                        // Position in the unpadded input, skipped when in the padding.
+                        let i = o * s + t * d;
+                        if i < p || i - p >= l {
+                            continue;
+                        }
+                        terms.push(&kernel[c * k + t] * &x[c * l + i - p]);
+                    }
+                }
+                out.push(terms.into_iter().sum::<Value>() + bias);
+            }
+        }
+
+        out
+    }
+
+    fn parameters(&self) -> Vec<Value> {
+        self.weights
+            .iter()
+            .flatten()
+            .chain(&self.biases)
+            .cloned()
+            .collect()
+    }
+
+    fn named_parameters(&self) -> Vec<(String, Value)> {
+        named_kernels(&self.weights, &self.biases)
+    }
+}
+
+impl fmt::Display for Conv1d {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (c, len) = self.output_shape();
+        write!(
+            f,
+            "conv1d({}×{}, kernel {}, stride {}, padding {}, dilation {}) → {}×{}",
+            self.in_channels,
+            self.input_len,
+            self.kernel_size,
+            self.stride,
+            self.padding,
+            self.dilation,
+            c,
+            len
+        )
+    }
+}
+
+impl fmt::Debug for Conv1d {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Conv1d")
+            .field("in_channels", &self.in_channels)
+            .field("out_channels", &self.weights.len())
+            .field("kernel_size", &self.kernel_size)
+            .field("stride", &self.stride)
+            .field("padding", &self.padding)
+            .field("dilation", &self.dilation)
+            .field("input_len", &self.input_len)
+            .finish()
+    }
+}
+
+fn named_kernels(weights: &[Vec<Value>], biases: &[Value]) -> Vec<(String, Value)> {
+    let weights = weights.iter().enumerate().flat_map(|(o, kernel)| {
+        kernel
+            .iter()
+            .enumerate()
+            .map(move |(i, w_i)| (format!("kernel{}.w{}", o, i), w_i.clone()))
+    });
+    let biases = biases
+        .iter()
+        .enumerate()
+        .map(|(o, b)| (format!("kernel{}.b", o), b.clone()));
+    weights.chain(biases).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::{assert_gradients, test_inputs, weighted_sum};
+
+    #[test]
+    fn conv2d_output_shape() {
+        assert_eq!(
+            Conv2d::new(1, 4, 5, 2, 0, (28, 28)).output_shape(),
+            (4, 12, 12)
+        );
+        assert_eq!(Conv2d::new(1, 1, 3, 1, 1, (3, 3)).output_shape(), (1, 3, 3));
+        assert_eq!(Conv2d::new(1, 1, 5, 1, 1, (3, 3)).output_shape(), (1, 1, 1));
+    }
+
+    #[test]
+    #[should_panic(expected = "Kernel is larger than the padded input")]
+    fn conv2d_kernel_larger_than_input() {
+        Conv2d::new(1, 1, 5, 1, 0, (3, 3));
+    }
+
+    #[test]
+    #[should_panic(expected = "Stride must be positive")]
+    fn conv2d_zero_stride() {
+        Conv2d::new(1, 1, 2, 0, 0, (3, 3));
+    }
+
+    #[test]
+    #[should_panic(expected = "Kernel size must be positive")]
+    fn conv2d_zero_kernel() {
+        Conv2d::new(1, 1, 0, 1, 0, (3, 3));
+    }
+
+    #[test]
+    fn conv1d_gradients() {
+        // Dilation 2 and padding 2, so that some kernel taps fall in the padding.
+        let conv = Conv1d::new(2, 3, 3, 2, 2, 2, 9);
+        assert_eq!(conv.output_shape(), (3, 5));
+
+        let x = test_inputs(2 * 9);
+        let mut inputs = x.clone();
+        inputs.extend(conv.parameters());
+        assert_gradients(|| weighted_sum(&conv.forw(&x)), &inputs, 1e-6);
+    }
+
+    #[test]
+    fn conv2d_gradients() {
+        let conv = Conv2d::new(2, 2, 3, 2, 1, (5, 4));
+        assert_eq!(conv.output_shape(), (2, 3, 2));
+
+        let x = test_inputs(2 * 5 * 4);
+        let mut inputs = x.clone();
+        inputs.extend(conv.parameters());
+        assert_gradients(|| weighted_sum(&conv.forw(&x)), &inputs, 1e-6);
+    }
+
+    #[test]
+    #[should_panic(expected = "Stride must be positive")]
+    fn conv1d_zero_stride() {
+        Conv1d::new(1, 1, 2, 0, 0, 1, 8);
+    }
+
+    #[test]
+    #[should_panic(expected = "Kernel size must be positive")]
+    fn conv1d_zero_kernel() {
+        Conv1d::new(1, 1, 0, 1, 0, 1, 8);
+    }
+
+    #[test]
+    #[should_panic(expected = "Dilation must be positive")]
+    fn conv1d_zero_dilation() {
+        Conv1d::new(1, 1, 2, 1, 0, 0, 8);
+    }
+}
@@ -0,0 +1,70 @@
+use crate::engine::Value;
+use crate::nn::Module;
+use crate::utils::with_rng;
+use rand::Rng;
+use std::fmt;
+
+/**
+Zeroes each input with probability `p` during training, and scales the kept inputs by
+`1 / (1 - p)` so that no rescaling is needed in evaluation mode, where it is the identity.
+Masks are drawn from the library's RNG, see `utils::manual_seed`.
+*/
+pub struct Dropout {
+    p: f64,
+    training: bool,
+}
+
+impl Dropout {
+    pub fn new(p: f64) -> Dropout {
+        assert!(
+            (0.0..1.0).contains(&p),
+            "Dropout probability must be in [0, 1)"
+        );
+        Dropout { p, training: true }
+    }
+}
+
+impl Module for Dropout {
+    fn forw(&self, x: &[Value]) -> Vec<Value> {
+        if !self.training || self.p == 0.0 {
+            return x.to_vec();
+        }
+
+        let scale = 1.0 / (1.0 - self.p);
+        with_rng(|rng| {
+            x.iter()
+                .map(|x_i| {
+                    let mask = if rng.gen::<f64>() < self.p {
+                        0.0
+                    } else {
+                        scale
+                    };
+                    x_i * mask
+                })
+                .collect()
+        })
+    }
+
+    fn parameters(&self) -> Vec<Value> {
+        vec![]
+    }
+
+    fn set_training(&mut self, training: bool) {
+        self.training = training;
+    }
+}
+
+impl fmt::Display for Dropout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "dropout({})", self.p)
+    }
+}
+
+impl fmt::Debug for Dropout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Dropout")
+            .field("p", &self.p)
+            .field("training", &self.training)
+            .finish()
+    }
+}
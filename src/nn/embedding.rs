@@ -0,0 +1,143 @@
+use crate::engine::Value;
+use crate::nn::{Init, Module};
+use crate::utils::with_rng;
+use std::fmt;
+
+/**
+Lookup table of learnable vectors, one row per index, drawn from Normal(0, 1).
+- `num_embeddings`: Number of indices, such as the size of a vocabulary.
+- `embedding_dim`: Length of each vector.
+
+Looking up an index returns the Values of its row, so gradients only accumulate into the rows
+that were used. Optimisers built `with_sparse_updates()` leave the other rows untouched.
+*/
+pub struct Embedding {
+    weights: Vec<Vec<Value>>,
+}
+
+impl Embedding {
+    pub fn new(num_embeddings: u32, embedding_dim: u32) -> Embedding {
+        Embedding::with_init(num_embeddings, embedding_dim, &Init::Normal(0.0, 1.0))
+    }
+
+    /// Embedding with rows drawn from the given initialisation scheme.
+    pub fn with_init(num_embeddings: u32, embedding_dim: u32, init: &Init) -> Embedding {
+        let (n, dim) = (num_embeddings as usize, embedding_dim as usize);
+        let weights = with_rng(|rng| init.sample(n, dim, n, dim, rng));
+
+        Embedding {
+            weights: weights.iter().map(|row| Value::new_1d(row)).collect(),
+        }
+    }
+
+    /// Rows of the given indices.
+    pub fn lookup(&self, indices: &[usize]) -> Vec<Vec<Value>> {
+        indices
+            .iter()
+            .map(|&i| {
+                assert!(
+                    i < self.weights.len(),
+                    "Index {} out of range for Embedding",
+                    i
+                );
+                self.weights[i].clone()
+            })
+            .collect()
+    }
+}
+
+impl Module for Embedding {
+    /// Concatenated rows of the indices in x, such as a row of categorical features.
+    /// Panics if an index is not a non-negative whole number.
+    fn forw(&self, x: &[Value]) -> Vec<Value> {
+        let indices: Vec<usize> = x
+            .iter()
+            .map(|x_i| {
+                let index = x_i.borrow().data;
+                assert!(
+                    index.is_finite() && index >= 0.0 && index.fract() == 0.0,
+                    "Embedding index must be a non-negative whole number, found {}",
+                    index
+                );
+                index as usize
+            })
+            .collect();
+        self.lookup(&indices).concat()
+    }
+
+    fn parameters(&self) -> Vec<Value> {
+        self.weights.iter().flatten().cloned().collect()
+    }
+
+    fn named_parameters(&self) -> Vec<(String, Value)> {
+        self.weights
+            .iter()
+            .enumerate()
+            .flat_map(|(i, row)| {
+                row.iter()
+                    .enumerate()
+                    .map(move |(j, w)| (format!("row{}.w{}", i, j), w.clone()))
+            })
+            .collect()
+    }
+}
+
+impl fmt::Display for Embedding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "embedding({}) → {}",
+            self.weights.len(),
+            self.weights.first().map_or(0, Vec::len)
+        )
+    }
+}
+
+impl fmt::Debug for Embedding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Embedding")
+            .field("num_embeddings", &self.weights.len())
+            .field("embedding_dim", &self.weights.first().map_or(0, Vec::len))
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn forward_concatenates_rows() {
+        let emb = Embedding::new(3, 2);
+        let out = emb.forw(&Value::new_1d(&[2.0, 0.0]));
+        let rows = emb.lookup(&[2, 0]).concat();
+        assert_eq!(out.len(), 4);
+        for (o, r) in out.iter().zip(rows.iter()) {
+            assert_eq!(o.borrow().data, r.borrow().data);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Embedding index must be a non-negative whole number, found 1.5")]
+    fn fractional_index() {
+        Embedding::new(3, 2).forw(&[Value::new(1.5)]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Embedding index must be a non-negative whole number, found -1")]
+    fn negative_index() {
+        Embedding::new(3, 2).forw(&[Value::new(-1.0)]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Embedding index must be a non-negative whole number, found NaN")]
+    fn nan_index() {
+        Embedding::new(3, 2).forw(&[Value::new(f64::NAN)]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Index 3 out of range for Embedding")]
+    fn index_out_of_range() {
+        Embedding::new(3, 2).forw(&[Value::new(3.0)]);
+    }
+}
@@ -0,0 +1,158 @@
+use rand::{distributions::Uniform, Rng};
+use std::{f64::consts::PI, fmt};
+
+/**
+Initialisation scheme for the weights or the biases of a `Layer`.
+- `fan_in`: Number of inputs of the layer.
+- `fan_out`: Number of outputs of the layer.
+*/
+pub enum Init {
+    /// Uniform(low, high).
+    Uniform(f64, f64),
+    /// Normal(mean, std).
+    Normal(f64, f64),
+    /// Glorot et al., Uniform(-a, a) with a = √(6 / (fan_in + fan_out)).
+    XavierUniform,
+    /// Glorot et al., Normal(0, std) with std = √(2 / (fan_in + fan_out)).
+    XavierNormal,
+    /// He et al., Uniform(-a, a) with a = √(6 / fan_in).
+    HeUniform,
+    /// He et al., Normal(0, std) with std = √(2 / fan_in).
+    HeNormal,
+    /// LeCun et al., Uniform(-a, a) with a = √(3 / fan_in).
+    LeCunUniform,
+    /// LeCun et al., Normal(0, std) with std = √(1 / fan_in).
+    LeCunNormal,
+    /// Saxe et al., (semi-)orthogonal matrix from the QR decomposition of a Normal(0, 1) matrix.
+    Orthogonal,
+    Constant(f64),
+    Zeros,
+    /// User closure, called with (fan_in, fan_out) for each value.
+    Custom(Box<dyn Fn(usize, usize) -> f64>),
+}
+
+impl Init {
+    /// Matrix of `rows` x `cols` initial values.
+    pub fn sample<R: Rng>(
+        &self,
+        rows: usize,
+        cols: usize,
+        fan_in: usize,
+        fan_out: usize,
+        rng: &mut R,
+    ) -> Vec<Vec<f64>> {
+        let (fan_in_f, fan_out_f) = (fan_in as f64, fan_out as f64);
+
+        match self {
+            Init::Uniform(low, high) => uniform(rows, cols, *low, *high, rng),
+            Init::Normal(mean, std) => normal(rows, cols, *mean, *std, rng),
+            Init::XavierUniform => {
+                let a = (6.0 / (fan_in_f + fan_out_f)).sqrt();
+                uniform(rows, cols, -a, a, rng)
+            }
+            Init::XavierNormal => {
+                let std = (2.0 / (fan_in_f + fan_out_f)).sqrt();
+                normal(rows, cols, 0.0, std, rng)
+            }
+            Init::HeUniform => {
+                let a = (6.0 / fan_in_f).sqrt();
+                uniform(rows, cols, -a, a, rng)
+            }
+            Init::HeNormal => {
+                let std = (2.0 / fan_in_f).sqrt();
+                normal(rows, cols, 0.0, std, rng)
+            }
+            Init::LeCunUniform => {
+                let a = (3.0 / fan_in_f).sqrt();
+                uniform(rows, cols, -a, a, rng)
+            }
+            Init::LeCunNormal => {
+                let std = (1.0 / fan_in_f).sqrt();
+                normal(rows, cols, 0.0, std, rng)
+            }
+            Init::Orthogonal => orthogonal(rows, cols, rng),
+            Init::Constant(c) => vec![vec![*c; cols]; rows],
+            Init::Zeros => vec![vec![0.0; cols]; rows],
+            Init::Custom(f) => (0..rows)
+                .map(|_| (0..cols).map(|_| f(fan_in, fan_out)).collect())
+                .collect(),
+        }
+    }
+}
+
+impl fmt::Debug for Init {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Init::Uniform(low, high) => write!(f, "Uniform({}, {})", low, high),
+            Init::Normal(mean, std) => write!(f, "Normal({}, {})", mean, std),
+            Init::XavierUniform => write!(f, "XavierUniform"),
+            Init::XavierNormal => write!(f, "XavierNormal"),
+            Init::HeUniform => write!(f, "HeUniform"),
+            Init::HeNormal => write!(f, "HeNormal"),
+            Init::LeCunUniform => write!(f, "LeCunUniform"),
+            Init::LeCunNormal => write!(f, "LeCunNormal"),
+            Init::Orthogonal => write!(f, "Orthogonal"),
+            Init::Constant(c) => write!(f, "Constant({})", c),
+            Init::Zeros => write!(f, "Zeros"),
+            Init::Custom(_) => write!(f, "Custom"),
+        }
+    }
+}
+
+fn uniform<R: Rng>(rows: usize, cols: usize, low: f64, high: f64, rng: &mut R) -> Vec<Vec<f64>> {
+    if low == high {
+        return vec![vec![low; cols]; rows];
+    }
+
+    let range = Uniform::<f64>::new(low, high);
+    (0..rows)
+        .map(|_| (0..cols).map(|_| rng.sample(range)).collect())
+        .collect()
+}
+
+fn normal<R: Rng>(rows: usize, cols: usize, mean: f64, std: f64, rng: &mut R) -> Vec<Vec<f64>> {
+    (0..rows)
+        .map(|_| {
+            (0..cols)
+                .map(|_| mean + std * standard_normal(rng))
+                .collect()
+        })
+        .collect()
+}
+
+// Box-Muller transform.
+fn standard_normal<R: Rng>(rng: &mut R) -> f64 {
+    let u1: f64 = 1.0 - rng.gen::<f64>(); // (0, 1]
+    let u2: f64 = rng.gen();
+    (-2.0 * u1.ln()).sqrt() * (2.0 * PI * u2).cos()
+}
+
+// Orthonormalises the rows of a Normal(0, 1) matrix with Gram-Schmidt, using the transpose
+// when there are more rows than columns.
+fn orthogonal<R: Rng>(rows: usize, cols: usize, rng: &mut R) -> Vec<Vec<f64>> {
+    let (n, m) = if rows <= cols {
+        (rows, cols)
+    } else {
+        (cols, rows)
+    };
+    let mut q = normal(n, m, 0.0, 1.0, rng);
+
+    for i in 0..n {
+        for j in 0..i {
+            let dot: f64 = q[i].iter().zip(&q[j]).map(|(a, b)| a * b).sum();
+            let q_j = q[j].clone();
+            q[i].iter_mut().zip(q_j).for_each(|(a, b)| *a -= dot * b);
+        }
+
+        let norm = q[i].iter().map(|a| a * a).sum::<f64>().sqrt();
+        q[i].iter_mut().for_each(|a| *a /= norm);
+    }
+
+    if rows <= cols {
+        q
+    } else {
+        (0..rows)
+            .map(|r| (0..cols).map(|c| q[c][r]).collect())
+            .collect()
+    }
+}
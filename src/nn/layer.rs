@@ -1,40 +1,194 @@
 use crate::engine::{ActvFn, Value};
-use crate::nn::Neuron;
+use crate::nn::{BatchNorm1d, Dropout, Init, LayerActvFn, Module, Neuron, Softmax};
+use crate::utils::with_rng;
 use std::fmt;
 
 pub struct Layer {
     neurons: Vec<Neuron>,
+    softmax: bool,
+    batch_norm: Option<BatchNorm1d>,
+    dropout: Option<Dropout>,
 }
 
 impl Layer {
     pub fn new(nin: u32, nout: u32, nonlin: Option<ActvFn>) -> Layer {
         Layer {
             neurons: (0..nout).map(|_| Neuron::new(nin, nonlin)).collect(),
+            softmax: false,
+            batch_norm: None,
+            dropout: None,
         }
     }
 
-    /// Forward a single 1d input x through the Layer.
-    pub fn forw(&self, x: &[Value]) -> Vec<Value> {
-        self.neurons.iter().map(|n| n.forw(x)).collect()
+    /// Layer with an element-wise activation function or softmax over its outputs.
+    pub fn with_actv_fn(nin: u32, nout: u32, actv_fn: Option<LayerActvFn>) -> Layer {
+        match actv_fn {
+            Some(LayerActvFn::ActvFn(actv_fn)) => Layer::new(nin, nout, Some(actv_fn)),
+            Some(LayerActvFn::Softmax) => Layer {
+                softmax: true,
+                ..Layer::new(nin, nout, None)
+            },
+            None => Layer::new(nin, nout, None),
+        }
+    }
+
+    /// Layer with weights and biases drawn from the given initialisation schemes.
+    pub fn with_init(
+        nin: u32,
+        nout: u32,
+        nonlin: Option<ActvFn>,
+        weight_init: &Init,
+        bias_init: &Init,
+    ) -> Layer {
+        let (nin, nout) = (nin as usize, nout as usize);
+
+        let (weights, biases) = with_rng(|rng| {
+            let weights = weight_init.sample(nout, nin, nin, nout, rng);
+            let biases = bias_init.sample(1, nout, nin, nout, rng).remove(0);
+            (weights, biases)
+        });
+
+        Layer {
+            neurons: weights
+                .iter()
+                .zip(biases)
+                .map(|(w, b)| Neuron::from_data(w, b, nonlin))
+                .collect(),
+            softmax: false,
+            batch_norm: None,
+            dropout: None,
+        }
+    }
+}
+
+impl Layer {
+    /// Apply batch normalization to the outputs of the Layer, before the activation function.
+    pub fn with_batch_norm(mut self, momentum: f64, epsilon: f64) -> Layer {
+        let nout = self.neurons.len() as u32;
+        self.batch_norm = Some(BatchNorm1d::new(nout, momentum, epsilon));
+        self
+    }
+
+    /// Apply dropout with probability `p` to the outputs of the Layer.
+    pub fn with_dropout(mut self, p: f64) -> Layer {
+        self.dropout = Some(Dropout::new(p));
+        self
+    }
+
+    /// Freeze the parameters of the Layer, leaving them out of `trainable_parameters()`.
+    pub fn freeze(&self) {
+        self.parameters().iter().for_each(Value::freeze);
+    }
+
+    pub fn unfreeze(&self) {
+        self.parameters().iter().for_each(Value::unfreeze);
+    }
+
+    // Activation function, softmax and dropout, applied to a row of pre-activations.
+    fn activate(&self, act: Vec<Value>) -> Vec<Value> {
+        let out: Vec<Value> = self
+            .neurons
+            .iter()
+            .zip(act)
+            .map(|(n, act_i)| n.activate(act_i))
+            .collect();
+
+        let out = if self.softmax {
+            Softmax.forw(&out)
+        } else {
+            out
+        };
+
+        match &self.dropout {
+            Some(dropout) => dropout.forw(&out),
+            None => out,
+        }
+    }
+}
+
+impl Module for Layer {
+    /// Forward a single 1d input x through the Layer. With batch normalization, panics in
+    /// training mode, which needs a batch.
+    fn forw(&self, x: &[Value]) -> Vec<Value> {
+        match &self.batch_norm {
+            Some(_) => self.forward(&[x.to_vec()]).remove(0),
+            None => self.activate(self.neurons.iter().map(|n| n.linear(x)).collect()),
+        }
     }
 
     /// Forward pass of 2d input x through the Layer.
-    pub fn forward(&self, x: &[&[Value]]) -> Vec<Vec<Value>> {
-        x.iter().map(|xrow| self.forw(xrow)).collect()
+    fn forward(&self, x: &[Vec<Value>]) -> Vec<Vec<Value>> {
+        let act: Vec<Vec<Value>> = x
+            .iter()
+            .map(|xrow| self.neurons.iter().map(|n| n.linear(xrow)).collect())
+            .collect();
+
+        let act = match &self.batch_norm {
+            Some(batch_norm) => batch_norm.forward(&act),
+            None => act,
+        };
+
+        act.into_iter().map(|row| self.activate(row)).collect()
     }
 
-    pub fn parameters(&self) -> Vec<Value> {
-        self.neurons.iter().flat_map(|n| n.parameters()).collect()
+    fn parameters(&self) -> Vec<Value> {
+        let neurons = self.neurons.iter().flat_map(|n| n.parameters());
+        let batch_norm = self.batch_norm.iter().flat_map(|bn| bn.parameters());
+        neurons.chain(batch_norm).collect()
+    }
+
+    fn named_parameters(&self) -> Vec<(String, Value)> {
+        let neurons = self.neurons.iter().enumerate().flat_map(|(i, n)| {
+            n.named_parameters()
+                .into_iter()
+                .map(move |(name, p)| (format!("neuron{}.{}", i, name), p))
+        });
+        let batch_norm = self.batch_norm.iter().flat_map(|bn| {
+            bn.named_parameters()
+                .into_iter()
+                .map(|(name, p)| (format!("batch_norm.{}", name), p))
+        });
+        neurons.chain(batch_norm).collect()
+    }
+
+    fn buffers(&self) -> Vec<Value> {
+        self.batch_norm.iter().flat_map(|bn| bn.buffers()).collect()
+    }
+
+    fn set_training(&mut self, training: bool) {
+        if let Some(batch_norm) = &mut self.batch_norm {
+            batch_norm.set_training(training);
+        }
+        if let Some(dropout) = &mut self.dropout {
+            dropout.set_training(training);
+        }
     }
 }
 
 impl fmt::Display for Layer {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self.neurons.first() {
+            Some(neuron) if self.softmax => {
+                write!(
+                    f,
+                    "{}({}) → {}",
+                    Softmax,
+                    neuron.weights.len(),
+                    self.neurons.len()
+                )?;
+            }
             Some(neuron) => {
-                write!(f, "{} → {}", neuron, self.neurons.len())
+                write!(f, "{} → {}", neuron, self.neurons.len())?;
             }
-            None => write!(f, "Empty"),
+            None => write!(f, "Empty")?,
+        }
+
+        if let Some(batch_norm) = &self.batch_norm {
+            write!(f, ", {}", batch_norm)?;
+        }
+        match &self.dropout {
+            Some(dropout) => write!(f, ", {}", dropout),
+            None => Ok(()),
         }
     }
 }
@@ -44,6 +198,15 @@ impl fmt::Debug for Layer {
         let mut debug_struct = f.debug_struct("Layer");
         debug_struct.field("neurons", &self.neurons[0]);
         debug_struct.field("output", &self.neurons.len());
+        if self.softmax {
+            debug_struct.field("actv_fn", &Softmax);
+        }
+        if let Some(batch_norm) = &self.batch_norm {
+            debug_struct.field("batch_norm", batch_norm);
+        }
+        if let Some(dropout) = &self.dropout {
+            debug_struct.field("dropout", dropout);
+        }
         debug_struct.finish()
     }
 }
@@ -1,13 +1,108 @@
 use crate::engine::{ActvFn, Value};
-use crate::nn::Layer;
+use crate::nn::{Init, Layer, LayerActvFn, Module};
 use std::fmt;
+use std::iter;
 
 pub struct MultiLayerPerceptron {
     layers: Vec<Layer>,
 }
 
 impl MultiLayerPerceptron {
-    pub fn new(nin: u32, mut nouts: Vec<u32>, actv_fn: ActvFn) -> MultiLayerPerceptron {
+    pub fn new(nin: u32, nouts: Vec<u32>, actv_fn: ActvFn) -> MultiLayerPerceptron {
+        MultiLayerPerceptron::build(nin, nouts, actv_fn, Layer::new)
+    }
+
+    /// MLP with weights and biases of every layer drawn from the given initialisation schemes.
+    pub fn with_init(
+        nin: u32,
+        nouts: Vec<u32>,
+        actv_fn: ActvFn,
+        weight_init: &Init,
+        bias_init: &Init,
+    ) -> MultiLayerPerceptron {
+        MultiLayerPerceptron::build(nin, nouts, actv_fn, |nin, nout, nonlin| {
+            Layer::with_init(nin, nout, nonlin, weight_init, bias_init)
+        })
+    }
+
+    /**
+    MLP with an activation function for each layer, including the output layer.
+    - `actv_fns`: Activation function of each layer, `None` for a linear layer.
+    */
+    pub fn with_actv_fns(
+        nin: u32,
+        nouts: Vec<u32>,
+        actv_fns: Vec<Option<LayerActvFn>>,
+    ) -> MultiLayerPerceptron {
+        assert!(
+            nouts.len() == actv_fns.len(),
+            "Mismatching number of layers and activation functions"
+        );
+
+        let nins = iter::once(nin).chain(nouts.clone());
+        let layers = nins
+            .zip(nouts)
+            .zip(actv_fns)
+            .map(|((nin, nout), actv_fn)| Layer::with_actv_fn(nin, nout, actv_fn))
+            .collect();
+
+        MultiLayerPerceptron { layers }
+    }
+
+    /// Apply batch normalization to every hidden layer, before its activation function.
+    pub fn with_batch_norm(mut self, momentum: f64, epsilon: f64) -> MultiLayerPerceptron {
+        let n = self.layers.len();
+        self.layers = self
+            .layers
+            .into_iter()
+            .enumerate()
+            .map(|(i, layer)| {
+                if i < n - 1 {
+                    layer.with_batch_norm(momentum, epsilon)
+                } else {
+                    layer
+                }
+            })
+            .collect();
+        self
+    }
+
+    /// Apply dropout with probability `p` to the outputs of every hidden layer.
+    pub fn with_dropout(mut self, p: f64) -> MultiLayerPerceptron {
+        let n = self.layers.len();
+        self.layers = self
+            .layers
+            .into_iter()
+            .enumerate()
+            .map(|(i, layer)| {
+                if i < n - 1 {
+                    layer.with_dropout(p)
+                } else {
+                    layer
+                }
+            })
+            .collect();
+        self
+    }
+
+    /// Layers of the MLP, such as `mlp.layers()[0].freeze()` to freeze the first layer.
+    pub fn layers(&self) -> &[Layer] {
+        &self.layers
+    }
+
+    /// Freeze the parameters of every layer, leaving them out of `trainable_parameters()`.
+    pub fn freeze(&self) {
+        self.layers.iter().for_each(Layer::freeze);
+    }
+
+    pub fn unfreeze(&self) {
+        self.layers.iter().for_each(Layer::unfreeze);
+    }
+
+    fn build<F>(nin: u32, mut nouts: Vec<u32>, actv_fn: ActvFn, layer: F) -> MultiLayerPerceptron
+    where
+        F: Fn(u32, u32, Option<ActvFn>) -> Layer,
+    {
         nouts.insert(0, nin);
         let n = nouts.len() - 1;
 
@@ -17,28 +112,54 @@ impl MultiLayerPerceptron {
                 let nout = nouts[i + 1];
                 let nonlin = if i == n - 1 { None } else { Some(actv_fn) }; // Last layer is linear
 
-                Layer::new(nin, nout, nonlin)
+                layer(nin, nout, nonlin)
             })
             .collect();
 
         MultiLayerPerceptron { layers }
     }
+}
 
+impl Module for MultiLayerPerceptron {
     /// Forward pass of a single 1d input x through the MLP.
-    pub fn forw(&self, x: &[Value]) -> Vec<Value> {
+    fn forw(&self, x: &[Value]) -> Vec<Value> {
         self.layers
             .iter()
             .fold(x.to_vec(), |x, layer| layer.forw(&x))
     }
 
     /// Forward pass of 2d input x through the MLP.
-    pub fn forward(&self, x: &[Vec<Value>]) -> Vec<Vec<Value>> {
-        x.iter().map(|xrow| self.forw(xrow)).collect()
+    fn forward(&self, x: &[Vec<Value>]) -> Vec<Vec<Value>> {
+        self.layers
+            .iter()
+            .fold(x.to_vec(), |x, layer| layer.forward(&x))
     }
 
-    pub fn parameters(&self) -> Vec<Value> {
+    fn parameters(&self) -> Vec<Value> {
         self.layers.iter().flat_map(|l| l.parameters()).collect()
     }
+
+    fn buffers(&self) -> Vec<Value> {
+        self.layers.iter().flat_map(|l| l.buffers()).collect()
+    }
+
+    fn named_parameters(&self) -> Vec<(String, Value)> {
+        self.layers
+            .iter()
+            .enumerate()
+            .flat_map(|(i, l)| {
+                l.named_parameters()
+                    .into_iter()
+                    .map(move |(name, p)| (format!("layer{}.{}", i, name), p))
+            })
+            .collect()
+    }
+
+    fn set_training(&mut self, training: bool) {
+        for layer in self.layers.iter_mut() {
+            layer.set_training(training);
+        }
+    }
 }
 
 impl fmt::Display for MultiLayerPerceptron {
@@ -1,11 +1,33 @@
 mod actv_fns;
+mod attention;
+mod conv;
+mod dropout;
+mod embedding;
+mod init;
 mod layer;
 mod mlp;
+mod module;
 mod neuron;
+mod norm;
+mod pool;
+mod residual;
+mod rnn;
+mod sequential;
 
 pub use actv_fns::*;
+pub use attention::*;
+pub use conv::*;
+pub use dropout::*;
+pub use embedding::*;
+pub use init::*;
 pub use layer::*;
 pub use mlp::*;
+pub use module::*;
 pub use neuron::*;
+pub use norm::*;
+pub use pool::*;
+pub use residual::*;
+pub use rnn::*;
+pub use sequential::*;
 
 pub mod optim;
@@ -0,0 +1,53 @@
+use crate::engine::Value;
+use std::fmt;
+
+/// Common interface of `Neuron`, `Layer`, `MultiLayerPerceptron` and user-defined models.
+pub trait Module: fmt::Display + fmt::Debug {
+    /// Forward pass of a single 1d input x.
+    fn forw(&self, x: &[Value]) -> Vec<Value>;
+
+    /// Forward pass of 2d input x.
+    fn forward(&self, x: &[Vec<Value>]) -> Vec<Vec<Value>> {
+        x.iter().map(|xrow| self.forw(xrow)).collect()
+    }
+
+    fn parameters(&self) -> Vec<Value>;
+
+    /// Parameters that are not frozen, to pass to an optimiser when fine-tuning.
+    fn trainable_parameters(&self) -> Vec<Value> {
+        self.parameters()
+            .into_iter()
+            .filter(|p| !p.is_frozen())
+            .collect()
+    }
+
+    /// Parameters paired with their path in the module, such as `layer0.neuron1.w2`.
+    fn named_parameters(&self) -> Vec<(String, Value)> {
+        self.parameters()
+            .into_iter()
+            .enumerate()
+            .map(|(i, p)| (i.to_string(), p))
+            .collect()
+    }
+
+    /// State that is saved and loaded with the model, but not trained, such as the running
+    /// statistics of `BatchNorm1d`.
+    fn buffers(&self) -> Vec<Value> {
+        vec![]
+    }
+
+    /// Switch between training and evaluation mode, which changes the behaviour of `Dropout`
+    /// and `BatchNorm1d`.
+    /// Containers pass it on to the modules they hold.
+    fn set_training(&mut self, _training: bool) {}
+
+    /// Set training mode.
+    fn train(&mut self) {
+        self.set_training(true);
+    }
+
+    /// Set evaluation mode.
+    fn eval(&mut self) {
+        self.set_training(false);
+    }
+}
@@ -1,4 +1,6 @@
 use crate::engine::{ActvFn, Op, Value};
+use crate::nn::Module;
+use crate::utils::with_rng;
 use rand::{distributions::Uniform, Rng};
 use std::fmt;
 
@@ -6,45 +8,84 @@ pub struct Neuron {
     pub weights: Vec<Value>,
     pub bias: Value,
     pub actv_fn: Option<ActvFn>,
+    /// Learnable slope of `ActvFn::PReLU`.
+    pub slope: Option<Value>,
 }
 
 impl Neuron {
     pub fn new(nin: u32, nonlin: Option<ActvFn>) -> Neuron {
-        let mut rng = rand::thread_rng();
         let range = Uniform::<f64>::new(-1., 1.);
 
         Neuron {
-            weights: (0..nin).map(|_| Value::new(rng.sample(range))).collect(),
+            weights: with_rng(|rng| (0..nin).map(|_| Value::new(rng.sample(range))).collect()),
             bias: Value::new(0.),
             actv_fn: nonlin,
+            slope: prelu_slope(nonlin),
         }
     }
 
-    pub fn forw(&self, x: &[Value]) -> Value {
-        let act = self
-            .weights
+    /// Neuron with the given initial weights and bias.
+    pub fn from_data(weights: &[f64], bias: f64, nonlin: Option<ActvFn>) -> Neuron {
+        Neuron {
+            weights: Value::new_1d(weights),
+            bias: Value::new(bias),
+            actv_fn: nonlin,
+            slope: prelu_slope(nonlin),
+        }
+    }
+}
+
+fn prelu_slope(nonlin: Option<ActvFn>) -> Option<Value> {
+    match nonlin {
+        Some(ActvFn::PReLU(slope)) => Some(Value::new(slope)),
+        _ => None,
+    }
+}
+
+impl Neuron {
+    /// Weighted sum of the input x and the bias, before the activation function.
+    pub fn linear(&self, x: &[Value]) -> Value {
+        self.weights
             .iter()
             .zip(x)
             .map(|(w_i, x_i)| w_i * x_i)
             .sum::<Value>()
-            + &self.bias;
+            + &self.bias
+    }
 
-        match self.actv_fn {
-            Some(ActvFn::ReLU) => act.relu(),
-            Some(ActvFn::LeakyReLU) => act.leaky_relu(),
-            Some(ActvFn::Tanh) => act.tanh(),
-            Some(ActvFn::Sigmoid) => act.sigmoid(),
-            None => act,
+    /// Apply the activation function of the Neuron to the output of `linear`.
+    pub fn activate(&self, act: Value) -> Value {
+        match (self.actv_fn, &self.slope) {
+            (Some(ActvFn::PReLU(_)), Some(slope)) => act.prelu(slope),
+            (Some(actv_fn), _) => act.activate(actv_fn),
+            (None, _) => act,
         }
     }
+}
 
-    pub fn forward(&self, x: &[Vec<Value>]) -> Vec<Value> {
-        x.iter().map(|x_i| self.forw(x_i)).collect()
+impl Module for Neuron {
+    fn forw(&self, x: &[Value]) -> Vec<Value> {
+        vec![self.activate(self.linear(x))]
     }
 
-    pub fn parameters(&self) -> Vec<Value> {
+    fn parameters(&self) -> Vec<Value> {
         let mut p = self.weights.clone();
         p.push(self.bias.clone());
+        p.extend(self.slope.clone());
+        p
+    }
+
+    fn named_parameters(&self) -> Vec<(String, Value)> {
+        let mut p: Vec<(String, Value)> = self
+            .weights
+            .iter()
+            .enumerate()
+            .map(|(i, w_i)| (format!("w{}", i), w_i.clone()))
+            .collect();
+        p.push(("b".to_string(), self.bias.clone()));
+        if let Some(slope) = &self.slope {
+            p.push(("slope".to_string(), slope.clone()));
+        }
         p
     }
 }
@@ -79,12 +120,14 @@ impl Neuron {
             .map(|wi| wi.clone().with_name('w'))
             .collect();
         let bias = self.bias.clone().with_name('b');
+        let slope = self.slope.map(|slope| slope.with_name('a'));
         let nonlin = self.actv_fn;
 
         Neuron {
             weights,
             bias,
             actv_fn: nonlin,
+            slope,
         }
     }
 
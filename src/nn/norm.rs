@@ -0,0 +1,302 @@
+use crate::engine::Value;
+use crate::nn::Module;
+use std::fmt;
+
+/**
+Batch normalization over the features of a 2d input.
+- In training mode, normalises with the mean and (biased) variance of the batch, and gradients
+  flow through them. The running averages are updated as
+  `running = (1 - momentum) * running + momentum * batch`, with the unbiased variance. Batches
+  need more than one sample, a single sample would be normalised to `beta`.
+- In evaluation mode, normalises with the running averages.
+
+The running averages are not returned by `parameters()`, but are saved and loaded with the model
+as `buffers()`.
+*/
+pub struct BatchNorm1d {
+    gamma: Vec<Value>,
+    beta: Vec<Value>,
+    running_mean: Vec<Value>,
+    running_var: Vec<Value>,
+    momentum: f64,
+    epsilon: f64,
+    training: bool,
+}
+
+impl BatchNorm1d {
+    pub fn new(num_features: u32, momentum: f64, epsilon: f64) -> BatchNorm1d {
+        let n = num_features as usize;
+
+        BatchNorm1d {
+            gamma: Value::new_1d(&vec![1.0; n]),
+            beta: Value::new_1d(&vec![0.0; n]),
+            running_mean: Value::new_1d(&vec![0.0; n]),
+            running_var: Value::new_1d(&vec![1.0; n]),
+            momentum,
+            epsilon,
+            training: true,
+        }
+    }
+
+    fn normalize_batch(&self, x: &[Vec<Value>]) -> Vec<Vec<Value>> {
+        assert!(
+            x.len() > 1,
+            "Expected more than 1 sample per batch in training mode, found {}",
+            x.len()
+        );
+        let n = x.len() as f64;
+        let mut out = vec![vec![]; x.len()];
+
+        for (j, (gamma, beta)) in self.gamma.iter().zip(&self.beta).enumerate() {
+            let col = x.iter().map(|xrow| &xrow[j]);
+            let mean = col.clone().cloned().sum::<Value>() / n;
+            let centered: Vec<Value> = col.map(|x_ij| x_ij - &mean).collect();
+            let var = centered.iter().map(|c| c.pow(2.0)).sum::<Value>() / n;
+            let inv_std = (&var + self.epsilon).pow(-0.5);
+
+            for (out_i, c) in out.iter_mut().zip(centered) {
+                out_i.push(c * &inv_std * gamma + beta);
+            }
+
+            let unbiased = n / (n - 1.0);
+            let mut running_mean = self.running_mean[j].borrow_mut();
+            let mut running_var = self.running_var[j].borrow_mut();
+            running_mean.data =
+                (1.0 - self.momentum) * running_mean.data + self.momentum * mean.borrow().data;
+            running_var.data = (1.0 - self.momentum) * running_var.data
+                + self.momentum * var.borrow().data * unbiased;
+        }
+
+        out
+    }
+
+    fn normalize_running(&self, x: &[Value]) -> Vec<Value> {
+        x.iter()
+            .zip(self.gamma.iter().zip(&self.beta))
+            .zip(self.running_mean.iter().zip(&self.running_var))
+            .map(|((x_j, (gamma, beta)), (mean, var))| {
+                let inv_std = (var.borrow().data + self.epsilon).powf(-0.5);
+                (x_j - mean.borrow().data) * inv_std * gamma + beta
+            })
+            .collect()
+    }
+}
+
+impl Module for BatchNorm1d {
+    /// Forward pass of a single 1d input x. Panics in training mode, which needs a batch.
+    fn forw(&self, x: &[Value]) -> Vec<Value> {
+        self.forward(&[x.to_vec()]).remove(0)
+    }
+
+    fn forward(&self, x: &[Vec<Value>]) -> Vec<Vec<Value>> {
+        if self.training {
+            self.normalize_batch(x)
+        } else {
+            x.iter().map(|xrow| self.normalize_running(xrow)).collect()
+        }
+    }
+
+    fn parameters(&self) -> Vec<Value> {
+        self.gamma.iter().chain(&self.beta).cloned().collect()
+    }
+
+    fn named_parameters(&self) -> Vec<(String, Value)> {
+        let gamma = self
+            .gamma
+            .iter()
+            .enumerate()
+            .map(|(i, g)| (format!("gamma{}", i), g));
+        let beta = self
+            .beta
+            .iter()
+            .enumerate()
+            .map(|(i, b)| (format!("beta{}", i), b));
+        gamma
+            .chain(beta)
+            .map(|(name, p)| (name, p.clone()))
+            .collect()
+    }
+
+    fn buffers(&self) -> Vec<Value> {
+        self.running_mean
+            .iter()
+            .chain(&self.running_var)
+            .cloned()
+            .collect()
+    }
+
+    fn set_training(&mut self, training: bool) {
+        self.training = training;
+    }
+}
+
+impl fmt::Display for BatchNorm1d {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "batch_norm({})", self.gamma.len())
+    }
+}
+
+impl fmt::Debug for BatchNorm1d {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BatchNorm1d")
+            .field("features", &self.gamma.len())
+            .field("momentum", &self.momentum)
+            .field("epsilon", &self.epsilon)
+            .field("training", &self.training)
+            .finish()
+    }
+}
+
+/// Layer normalization over the features of each sample, with learnable gamma and beta.
+pub struct LayerNorm {
+    gamma: Vec<Value>,
+    beta: Vec<Value>,
+    epsilon: f64,
+}
+
+impl LayerNorm {
+    pub fn new(num_features: u32, epsilon: f64) -> LayerNorm {
+        let n = num_features as usize;
+
+        LayerNorm {
+            gamma: Value::new_1d(&vec![1.0; n]),
+            beta: Value::new_1d(&vec![0.0; n]),
+            epsilon,
+        }
+    }
+}
+
+impl Module for LayerNorm {
+    fn forw(&self, x: &[Value]) -> Vec<Value> {
+        let n = x.len() as f64;
+        let mean = x.iter().cloned().sum::<Value>() / n;
+        let centered: Vec<Value> = x.iter().map(|x_j| x_j - &mean).collect();
+        let var = centered.iter().map(|c| c.pow(2.0)).sum::<Value>() / n;
+        let inv_std = (var + self.epsilon).pow(-0.5);
+
+        centered
+            .into_iter()
+            .zip(self.gamma.iter().zip(&self.beta))
+            .map(|(c, (gamma, beta))| c * &inv_std * gamma + beta)
+            .collect()
+    }
+
+    fn parameters(&self) -> Vec<Value> {
+        self.gamma.iter().chain(&self.beta).cloned().collect()
+    }
+
+    fn named_parameters(&self) -> Vec<(String, Value)> {
+        let gamma = self
+            .gamma
+            .iter()
+            .enumerate()
+            .map(|(i, g)| (format!("gamma{}", i), g));
+        let beta = self
+            .beta
+            .iter()
+            .enumerate()
+            .map(|(i, b)| (format!("beta{}", i), b));
+        gamma
+            .chain(beta)
+            .map(|(name, p)| (name, p.clone()))
+            .collect()
+    }
+}
+
+impl fmt::Display for LayerNorm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "layer_norm({})", self.gamma.len())
+    }
+}
+
+impl fmt::Debug for LayerNorm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LayerNorm")
+            .field("features", &self.gamma.len())
+            .field("epsilon", &self.epsilon)
+            .finish()
+    }
+}
+
+/// Root mean square normalization over the features of each sample, with learnable gamma.
+pub struct RMSNorm {
+    gamma: Vec<Value>,
+    epsilon: f64,
+}
+
+impl RMSNorm {
+    pub fn new(num_features: u32, epsilon: f64) -> RMSNorm {
+        RMSNorm {
+            gamma: Value::new_1d(&vec![1.0; num_features as usize]),
+            epsilon,
+        }
+    }
+}
+
+impl Module for RMSNorm {
+    fn forw(&self, x: &[Value]) -> Vec<Value> {
+        let n = x.len() as f64;
+        let mean_sq = x.iter().map(|x_j| x_j.pow(2.0)).sum::<Value>() / n;
+        let inv_rms = (mean_sq + self.epsilon).pow(-0.5);
+
+        x.iter()
+            .zip(&self.gamma)
+            .map(|(x_j, gamma)| x_j * &inv_rms * gamma)
+            .collect()
+    }
+
+    fn parameters(&self) -> Vec<Value> {
+        self.gamma.clone()
+    }
+
+    fn named_parameters(&self) -> Vec<(String, Value)> {
+        self.gamma
+            .iter()
+            .enumerate()
+            .map(|(i, g)| (format!("gamma{}", i), g.clone()))
+            .collect()
+    }
+}
+
+impl fmt::Display for RMSNorm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "rms_norm({})", self.gamma.len())
+    }
+}
+
+impl fmt::Debug for RMSNorm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RMSNorm")
+            .field("features", &self.gamma.len())
+            .field("epsilon", &self.epsilon)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[should_panic(expected = "Expected more than 1 sample per batch in training mode, found 1")]
+    fn single_sample_in_training() {
+        BatchNorm1d::new(2, 0.1, 1e-5).forw(&Value::new_1d(&[1.0, 2.0]));
+    }
+
+    #[test]
+    fn running_statistics() {
+        let mut bn = BatchNorm1d::new(1, 0.5, 1e-5);
+        let x = vec![Value::new_1d(&[1.0]), Value::new_1d(&[3.0])];
+        bn.forward(&x);
+
+        // Mean 2 and unbiased variance 2, halfway from 0 and 1.
+        assert_eq!(bn.running_mean[0].borrow().data, 1.0);
+        assert_eq!(bn.running_var[0].borrow().data, 1.5);
+
+        // Single samples are normalised with the running statistics in evaluation mode.
+        bn.eval();
+        let y = bn.forw(&Value::new_1d(&[2.0]));
+        assert!((y[0].borrow().data - 1.0 / (1.5_f64 + 1e-5).sqrt()).abs() < 1e-12);
+        assert_eq!(bn.running_mean[0].borrow().data, 1.0);
+    }
+}
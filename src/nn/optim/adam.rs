@@ -10,13 +10,15 @@ pub struct Adam {
     epsilon: f64,
     m: Vec<f64>,
     v: Vec<f64>,
-    t: usize,
+    t: Vec<usize>,
+    sparse: bool,
 }
 
 impl Adam {
     pub fn new(params: Vec<Value>, lr: f64, beta1: f64, beta2: f64, epsilon: f64) -> Adam {
         let m = vec![0.0; params.len()];
         let v = vec![0.0; params.len()];
+        let t = vec![0; params.len()];
 
         Adam {
             params,
@@ -26,25 +28,42 @@ impl Adam {
             epsilon,
             m,
             v,
-            t: 0,
+            t,
+            sparse: false,
         }
     }
 
-    pub fn step(&mut self) {
-        self.t += 1;
+    /**
+    Only update the parameters with a non-zero gradient, such as the rows of an `Embedding`
+    that were looked up. The moments and the step count of the other parameters are left as
+    they are, instead of decaying and moving the parameters with stale momentum.
+    A parameter is skipped whenever its gradient is exactly zero, even if it was used, such as
+    the weights behind a dead ReLU. A penalty that gives every parameter a gradient, such as
+    `l2_regularization`, makes every update dense.
+    */
+    pub fn with_sparse_updates(mut self) -> Adam {
+        self.sparse = true;
+        self
+    }
 
-        for (param, (m_t, v_t)) in self
-            .params
-            .iter()
-            .zip(self.m.iter_mut().zip(self.v.iter_mut()))
-        {
+    pub fn step(&mut self) {
+        for (param, ((m_t, v_t), t)) in self.params.iter().zip(
+            self.m
+                .iter_mut()
+                .zip(self.v.iter_mut())
+                .zip(self.t.iter_mut()),
+        ) {
             let grad = param.borrow().grad;
+            if self.sparse && grad == 0.0 {
+                continue;
+            }
+            *t += 1;
 
             *m_t = self.beta1 * *m_t + (1.0 - self.beta1) * grad;
             *v_t = self.beta2 * *v_t + (1.0 - self.beta2) * grad.powi(2);
 
-            let mc_t = *m_t / (1.0 - self.beta1.powi(self.t as i32));
-            let vc_t = *v_t / (1.0 - self.beta2.powi(self.t as i32));
+            let mc_t = *m_t / (1.0 - self.beta1.powi(*t as i32));
+            let vc_t = *v_t / (1.0 - self.beta2.powi(*t as i32));
 
             param.borrow_mut().data -= self.lr * mc_t / (vc_t.sqrt() + self.epsilon);
         }
@@ -64,6 +83,7 @@ impl fmt::Debug for Adam {
             .field("beta1", &self.beta1)
             .field("beta2", &self.beta2)
             .field("epsilon", &self.epsilon)
+            .field("sparse", &self.sparse)
             .finish()
     }
 }
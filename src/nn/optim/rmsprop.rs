@@ -8,6 +8,7 @@ pub struct RMSprop {
     epsilon: f64,
     v: Vec<f64>,
     t: usize,
+    sparse: bool,
 }
 
 impl RMSprop {
@@ -21,14 +22,30 @@ impl RMSprop {
             epsilon,
             v,
             t: 0,
+            sparse: false,
         }
     }
 
+    /**
+    Only update the parameters with a non-zero gradient, such as the rows of an `Embedding`
+    that were looked up.
+    A parameter is skipped whenever its gradient is exactly zero, even if it was used, such as
+    the weights behind a dead ReLU. A penalty that gives every parameter a gradient, such as
+    `l2_regularization`, makes every update dense.
+    */
+    pub fn with_sparse_updates(mut self) -> RMSprop {
+        self.sparse = true;
+        self
+    }
+
     pub fn step(&mut self) {
         self.t += 1;
 
         for (param, v_t) in self.params.iter().zip(self.v.iter_mut()) {
             let grad = param.borrow().grad;
+            if self.sparse && grad == 0.0 {
+                continue;
+            }
 
             *v_t = self.beta * *v_t + (1.0 - self.beta) * grad.powi(2);
 
@@ -49,6 +66,7 @@ impl fmt::Debug for RMSprop {
             .field("lr", &self.lr)
             .field("beta", &self.beta)
             .field("epsilon", &self.epsilon)
+            .field("sparse", &self.sparse)
             .finish()
     }
 }
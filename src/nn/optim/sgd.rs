@@ -6,6 +6,7 @@ pub struct SGD {
     lr: f64,
     momentum: f64,
     velocities: Vec<f64>,
+    sparse: bool,
 }
 
 impl SGD {
@@ -17,12 +18,30 @@ impl SGD {
             lr,
             momentum,
             velocities,
+            sparse: false,
         }
     }
 
+    /**
+    Only update the parameters with a non-zero gradient, such as the rows of an `Embedding`
+    that were looked up.
+    A parameter is skipped whenever its gradient is exactly zero, even if it was used, such as
+    the weights behind a dead ReLU. A penalty that gives every parameter a gradient, such as
+    `l2_regularization`, makes every update dense.
+    */
+    pub fn with_sparse_updates(mut self) -> SGD {
+        self.sparse = true;
+        self
+    }
+
     pub fn step(&mut self) {
         for (param, velocity) in self.params.iter().zip(self.velocities.iter_mut()) {
-            *velocity = self.momentum * (*velocity) + self.lr * param.borrow().grad;
+            let grad = param.borrow().grad;
+            if self.sparse && grad == 0.0 {
+                continue;
+            }
+
+            *velocity = self.momentum * (*velocity) + self.lr * grad;
             param.borrow_mut().data -= *velocity;
         }
     }
@@ -39,6 +58,7 @@ impl fmt::Debug for SGD {
         f.debug_struct("SGD")
             .field("lr", &self.lr)
             .field("momentum", &self.momentum)
+            .field("sparse", &self.sparse)
             .finish()
     }
 }
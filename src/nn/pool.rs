@@ -0,0 +1,408 @@
+use crate::engine::Value;
+use crate::nn::Module;
+use std::fmt;
+
+/// 2d max pooling over an input of shape `channels × height × width`, flattened in that order.
+pub struct MaxPool2d {
+    pool: Pool2d,
+}
+
+/// 2d average pooling over an input of shape `channels × height × width`, flattened in that order.
+pub struct AvgPool2d {
+    pool: Pool2d,
+}
+
+impl MaxPool2d {
+    pub fn new(channels: u32, kernel_size: u32, stride: u32, input_size: (u32, u32)) -> MaxPool2d {
+        MaxPool2d {
+            pool: Pool2d::new(channels, kernel_size, stride, input_size),
+        }
+    }
+
+    /// Shape of the output, `(channels, height, width)`.
+    pub fn output_shape(&self) -> (usize, usize, usize) {
+        self.pool.output_shape()
+    }
+}
+
+impl AvgPool2d {
+    pub fn new(channels: u32, kernel_size: u32, stride: u32, input_size: (u32, u32)) -> AvgPool2d {
+        AvgPool2d {
+            pool: Pool2d::new(channels, kernel_size, stride, input_size),
+        }
+    }
+
+    /// Shape of the output, `(channels, height, width)`.
+    pub fn output_shape(&self) -> (usize, usize, usize) {
+        self.pool.output_shape()
+    }
+}
+
+impl Module for MaxPool2d {
+    // The maximum is the input Value itself, so the gradient only reaches that input.
+    fn forw(&self, x: &[Value]) -> Vec<Value> {
+        self.pool.forw(x, |window| {
+            window
+                .into_iter()
+                .max()
+                .expect("Error in pooling empty window")
+        })
+    }
+
+    fn parameters(&self) -> Vec<Value> {
+        vec![]
+    }
+}
+
+impl Module for AvgPool2d {
+    fn forw(&self, x: &[Value]) -> Vec<Value> {
+        self.pool.forw(x, |window| {
+            let n = window.len() as f64;
+            window.into_iter().sum::<Value>() / n
+        })
+    }
+
+    fn parameters(&self) -> Vec<Value> {
+        vec![]
+    }
+}
+
+impl fmt::Display for MaxPool2d {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.pool.fmt_named(f, "max_pool2d")
+    }
+}
+
+impl fmt::Display for AvgPool2d {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.pool.fmt_named(f, "avg_pool2d")
+    }
+}
+
+impl fmt::Debug for MaxPool2d {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.pool.debug_named(f, "MaxPool2d")
+    }
+}
+
+impl fmt::Debug for AvgPool2d {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.pool.debug_named(f, "AvgPool2d")
+    }
+}
+
+// Windows shared by the 2d pooling layers.
+struct Pool2d {
+    channels: usize,
+    kernel_size: usize,
+    stride: usize,
+    input_size: (usize, usize),
+}
+
+impl Pool2d {
+    fn new(channels: u32, kernel_size: u32, stride: u32, input_size: (u32, u32)) -> Pool2d {
+        assert!(kernel_size > 0, "Kernel size must be positive");
+        assert!(stride > 0, "Stride must be positive");
+        assert!(
+            kernel_size <= input_size.0 && kernel_size <= input_size.1,
+            "Kernel is larger than the input"
+        );
+
+        Pool2d {
+            channels: channels as usize,
+            kernel_size: kernel_size as usize,
+            stride: stride as usize,
+            input_size: (input_size.0 as usize, input_size.1 as usize),
+        }
+    }
+
+    fn output_shape(&self) -> (usize, usize, usize) {
+        let (h, w) = self.input_size;
+        let (k, s) = (self.kernel_size, self.stride);
+        (self.channels, (h - k) / s + 1, (w - k) / s + 1)
+    }
+
+    fn forw<F: Fn(Vec<Value>) -> Value>(&self, x: &[Value], reduce: F) -> Vec<Value> {
+        let (h, w) = self.input_size;
+        let (k, s) = (self.kernel_size, self.stride);
+        let (_, out_h, out_w) = self.output_shape();
+        assert!(
+            x.len() == self.channels * h * w,
+            "Mismatching input size for pooling"
+        );
+
+        let mut out = Vec::with_capacity(self.channels * out_h * out_w);
+        for c in 0..self.channels {
+            for oy in 0..out_h {
+                for ox in 0..out_w {
+                    let window = (0..k)
+                        .flat_map(|ky| (0..k).map(move |kx| (oy * s + ky, ox * s + kx)))
+                        .map(|(iy, ix)| x[(c * h + iy) * w + ix].clone())
+                        .collect();
+                    out.push(reduce(window));
+                }
+            }
+        }
+
+        out
+    }
+
+    fn fmt_named(&self, f: &mut fmt::Formatter<'_>, name: &str) -> fmt::Result {
+        let (h, w) = self.input_size;
+        let (c, out_h, out_w) = self.output_shape();
+        write!(
+            f,
+            "{}({}×{}×{}, kernel {}, stride {}) → {}×{}×{}",
+            name, self.channels, h, w, self.kernel_size, self.stride, c, out_h, out_w
+        )
+    }
+
+    fn debug_named(&self, f: &mut fmt::Formatter<'_>, name: &str) -> fmt::Result {
+        f.debug_struct(name)
+            .field("channels", &self.channels)
+            .field("kernel_size", &self.kernel_size)
+            .field("stride", &self.stride)
+            .field("input_size", &self.input_size)
+            .finish()
+    }
+}
+
+/// 1d max pooling over an input of shape `channels × length`, flattened in that order.
+pub struct MaxPool1d {
+    channels: usize,
+    kernel_size: usize,
+    stride: usize,
+    input_len: usize,
+}
+
+impl MaxPool1d {
+    pub fn new(channels: u32, kernel_size: u32, stride: u32, input_len: u32) -> MaxPool1d {
+        assert!(kernel_size > 0, "Kernel size must be positive");
+        assert!(stride > 0, "Stride must be positive");
+        assert!(kernel_size <= input_len, "Kernel is larger than the input");
+
+        MaxPool1d {
+            channels: channels as usize,
+            kernel_size: kernel_size as usize,
+            stride: stride as usize,
+            input_len: input_len as usize,
+        }
+    }
+
+    /// Shape of the output, `(channels, length)`.
+    pub fn output_shape(&self) -> (usize, usize) {
+        let len = (self.input_len - self.kernel_size) / self.stride + 1;
+        (self.channels, len)
+    }
+}
+
+impl Module for MaxPool1d {
+    fn forw(&self, x: &[Value]) -> Vec<Value> {
+        let (k, s, l) = (self.kernel_size, self.stride, self.input_len);
+        let (_, out_l) = self.output_shape();
+        assert!(
+            x.len() == self.channels * l,
+            "Mismatching input size for MaxPool1d"
+        );
+
+        x.chunks(l)
+            .flat_map(|channel| {
+                (0..out_l).map(move |o| {
+                    channel[o * s..o * s + k]
+                        .iter()
+                        .max()
+                        .expect("Error in pooling empty window")
+                        .clone()
+                })
+            })
+            .collect()
+    }
+
+    fn parameters(&self) -> Vec<Value> {
+        vec![]
+    }
+}
+
+impl fmt::Display for MaxPool1d {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (c, len) = self.output_shape();
+        write!(
+            f,
+            "max_pool1d({}×{}, kernel {}, stride {}) → {}×{}",
+            self.channels, self.input_len, self.kernel_size, self.stride, c, len
+        )
+    }
+}
+
+impl fmt::Debug for MaxPool1d {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MaxPool1d")
+            .field("channels", &self.channels)
+            .field("kernel_size", &self.kernel_size)
+            .field("stride", &self.stride)
+            .field("input_len", &self.input_len)
+            .finish()
+    }
+}
+
+/// Mean of each channel of an input of shape `channels × length`, giving `channels` features.
+pub struct GlobalAvgPool1d {
+    channels: usize,
+    input_len: usize,
+}
+
+impl GlobalAvgPool1d {
+    pub fn new(channels: u32, input_len: u32) -> GlobalAvgPool1d {
+        GlobalAvgPool1d {
+            channels: channels as usize,
+            input_len: input_len as usize,
+        }
+    }
+}
+
+impl Module for GlobalAvgPool1d {
+    fn forw(&self, x: &[Value]) -> Vec<Value> {
+        let l = self.input_len;
+        assert!(
+            x.len() == self.channels * l,
+            "Mismatching input size for GlobalAvgPool1d"
+        );
+
+        x.chunks(l)
+            .map(|channel| channel.iter().cloned().sum::<Value>() / l as f64)
+            .collect()
+    }
+
+    fn parameters(&self) -> Vec<Value> {
+        vec![]
+    }
+}
+
+impl fmt::Display for GlobalAvgPool1d {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "global_avg_pool1d({}×{}) → {}",
+            self.channels, self.input_len, self.channels
+        )
+    }
+}
+
+impl fmt::Debug for GlobalAvgPool1d {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GlobalAvgPool1d")
+            .field("channels", &self.channels)
+            .field("input_len", &self.input_len)
+            .finish()
+    }
+}
+
+/**
+Marks the change from `channels × height × width` images to features for dense layers.
+Images are already flattened in that order, so the values pass through unchanged.
+*/
+pub struct Flatten {
+    shape: (usize, usize, usize),
+}
+
+impl Flatten {
+    pub fn new(shape: (usize, usize, usize)) -> Flatten {
+        Flatten { shape }
+    }
+}
+
+impl Module for Flatten {
+    fn forw(&self, x: &[Value]) -> Vec<Value> {
+        let (c, h, w) = self.shape;
+        assert!(x.len() == c * h * w, "Mismatching input size for Flatten");
+        x.to_vec()
+    }
+
+    fn parameters(&self) -> Vec<Value> {
+        vec![]
+    }
+}
+
+impl fmt::Display for Flatten {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (c, h, w) = self.shape;
+        write!(f, "flatten({}×{}×{}) → {}", c, h, w, c * h * w)
+    }
+}
+
+impl fmt::Debug for Flatten {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Flatten")
+            .field("shape", &self.shape)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::{assert_gradients, test_inputs, weighted_sum};
+
+    #[test]
+    fn max_pool1d_gradients() {
+        let pool = MaxPool1d::new(2, 3, 2, 9);
+        assert_eq!(pool.output_shape(), (2, 4));
+
+        let x = test_inputs(2 * 9);
+        assert_gradients(|| weighted_sum(&pool.forw(&x)), &x, 1e-6);
+    }
+
+    #[test]
+    fn global_avg_pool1d_gradients() {
+        let pool = GlobalAvgPool1d::new(3, 5);
+        let x = test_inputs(3 * 5);
+        assert_gradients(|| weighted_sum(&pool.forw(&x)), &x, 1e-6);
+    }
+
+    #[test]
+    fn pool2d_gradients() {
+        let (max, avg) = (
+            MaxPool2d::new(2, 2, 2, (4, 5)),
+            AvgPool2d::new(2, 3, 1, (4, 5)),
+        );
+        let x = test_inputs(2 * 4 * 5);
+        assert_gradients(|| weighted_sum(&max.forw(&x)), &x, 1e-6);
+        assert_gradients(|| weighted_sum(&avg.forw(&x)), &x, 1e-6);
+    }
+
+    #[test]
+    #[should_panic(expected = "Stride must be positive")]
+    fn max_pool1d_zero_stride() {
+        MaxPool1d::new(1, 2, 0, 8);
+    }
+
+    #[test]
+    #[should_panic(expected = "Stride must be positive")]
+    fn max_pool2d_zero_stride() {
+        MaxPool2d::new(1, 2, 0, (4, 4));
+    }
+
+    #[test]
+    #[should_panic(expected = "Stride must be positive")]
+    fn avg_pool2d_zero_stride() {
+        AvgPool2d::new(1, 2, 0, (4, 4));
+    }
+
+    #[test]
+    #[should_panic(expected = "Kernel size must be positive")]
+    fn max_pool1d_zero_kernel() {
+        MaxPool1d::new(1, 0, 1, 8);
+    }
+
+    #[test]
+    #[should_panic(expected = "Kernel size must be positive")]
+    fn max_pool2d_zero_kernel() {
+        MaxPool2d::new(1, 0, 1, (4, 4));
+    }
+
+    #[test]
+    #[should_panic(expected = "Kernel size must be positive")]
+    fn avg_pool2d_zero_kernel() {
+        AvgPool2d::new(1, 0, 1, (4, 4));
+    }
+}
@@ -0,0 +1,172 @@
+use crate::engine::Value;
+use crate::nn::{Init, Layer, Module};
+use std::fmt;
+
+/**
+Residual connection around a module, `f(x) + x`.
+When the module changes the width of its input, `with_projection` adds a linear projection of
+`x` to the width of `f(x)`.
+*/
+pub struct Residual {
+    module: Box<dyn Module>,
+    projection: Option<Layer>,
+}
+
+impl Residual {
+    pub fn new<M: Module + 'static>(module: M) -> Residual {
+        Residual {
+            module: Box::new(module),
+            projection: None,
+        }
+    }
+
+    /// Project the input from `nin` to `nout` features before adding it to the output.
+    pub fn with_projection(mut self, nin: u32, nout: u32) -> Residual {
+        let projection = Layer::with_init(nin, nout, None, &Init::XavierUniform, &Init::Zeros);
+        self.projection = Some(projection);
+        self
+    }
+
+    fn skip(&self, x: &[Value], fx: Vec<Value>) -> Vec<Value> {
+        let x = match &self.projection {
+            Some(projection) => projection.forw(x),
+            None => x.to_vec(),
+        };
+        assert!(
+            x.len() == fx.len(),
+            "Mismatching widths in Residual, add a projection"
+        );
+
+        fx.iter().zip(x).map(|(fx_i, x_i)| fx_i + x_i).collect()
+    }
+}
+
+impl Module for Residual {
+    fn forw(&self, x: &[Value]) -> Vec<Value> {
+        self.skip(x, self.module.forw(x))
+    }
+
+    // Batch-wise, for modules such as BatchNorm1d.
+    fn forward(&self, x: &[Vec<Value>]) -> Vec<Vec<Value>> {
+        let fx = self.module.forward(x);
+        x.iter()
+            .zip(fx)
+            .map(|(x_i, fx_i)| self.skip(x_i, fx_i))
+            .collect()
+    }
+
+    fn parameters(&self) -> Vec<Value> {
+        let mut params = self.module.parameters();
+        if let Some(projection) = &self.projection {
+            params.extend(projection.parameters());
+        }
+        params
+    }
+
+    fn named_parameters(&self) -> Vec<(String, Value)> {
+        let module = self.module.named_parameters().into_iter();
+        let projection = self
+            .projection
+            .iter()
+            .flat_map(|projection| projection.named_parameters());
+
+        module
+            .map(|(name, p)| (format!("module.{}", name), p))
+            .chain(projection.map(|(name, p)| (format!("projection.{}", name), p)))
+            .collect()
+    }
+
+    fn buffers(&self) -> Vec<Value> {
+        self.module.buffers()
+    }
+
+    fn set_training(&mut self, training: bool) {
+        self.module.set_training(training);
+    }
+}
+
+/// Skip connection that concatenates the output of a module with its input, `[f(x), x]`.
+pub struct ConcatSkip {
+    module: Box<dyn Module>,
+}
+
+impl ConcatSkip {
+    pub fn new<M: Module + 'static>(module: M) -> ConcatSkip {
+        ConcatSkip {
+            module: Box::new(module),
+        }
+    }
+}
+
+impl Module for ConcatSkip {
+    fn forw(&self, x: &[Value]) -> Vec<Value> {
+        [self.module.forw(x), x.to_vec()].concat()
+    }
+
+    // Batch-wise, for modules such as BatchNorm1d.
+    fn forward(&self, x: &[Vec<Value>]) -> Vec<Vec<Value>> {
+        let fx = self.module.forward(x);
+        x.iter()
+            .zip(fx)
+            .map(|(x_i, fx_i)| [fx_i, x_i.clone()].concat())
+            .collect()
+    }
+
+    fn parameters(&self) -> Vec<Value> {
+        self.module.parameters()
+    }
+
+    fn named_parameters(&self) -> Vec<(String, Value)> {
+        self.module
+            .named_parameters()
+            .into_iter()
+            .map(|(name, p)| (format!("module.{}", name), p))
+            .collect()
+    }
+
+    fn buffers(&self) -> Vec<Value> {
+        self.module.buffers()
+    }
+
+    fn set_training(&mut self, training: bool) {
+        self.module.set_training(training);
+    }
+}
+
+impl fmt::Display for Residual {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let module = self.module.to_string();
+        match &self.projection {
+            Some(projection) => write!(
+                f,
+                "residual({}, projection {})",
+                module.trim_end(),
+                projection
+            ),
+            None => write!(f, "residual({})", module.trim_end()),
+        }
+    }
+}
+
+impl fmt::Debug for Residual {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Residual")
+            .field("module", &self.module)
+            .field("projection", &self.projection)
+            .finish()
+    }
+}
+
+impl fmt::Display for ConcatSkip {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "concat_skip({})", self.module.to_string().trim_end())
+    }
+}
+
+impl fmt::Debug for ConcatSkip {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ConcatSkip")
+            .field("module", &self.module)
+            .finish()
+    }
+}
@@ -0,0 +1,462 @@
+use crate::engine::Value;
+use crate::nn::{Init, Layer, Module};
+use std::fmt;
+
+/**
+Hidden state of a recurrent cell.
+- `h`: Hidden state, the output of the cell.
+- `c`: Cell state, only used by `LSTMCell`.
+*/
+#[derive(Debug, Clone)]
+pub struct RecurrentState {
+    pub h: Vec<Value>,
+    pub c: Option<Vec<Value>>,
+}
+
+impl RecurrentState {
+    /// Copy of the state as new leaf Values, so that backpropagation stops at it.
+    pub fn detach(&self) -> RecurrentState {
+        let detach = |xs: &[Value]| -> Vec<Value> {
+            xs.iter().map(|x| Value::new(x.borrow().data)).collect()
+        };
+
+        RecurrentState {
+            h: detach(&self.h),
+            c: self.c.as_deref().map(detach),
+        }
+    }
+}
+
+/// Single time step of a recurrent layer, unrolled over a sequence by `Recurrent`.
+pub trait RecurrentCell: Module {
+    fn input_size(&self) -> usize;
+
+    fn hidden_size(&self) -> usize;
+
+    /// State of zeros, used at the start of a sequence.
+    fn init_state(&self) -> RecurrentState;
+
+    /// Next state from the input `x` of the time step and the previous state.
+    fn step(&self, x: &[Value], state: &RecurrentState) -> RecurrentState;
+}
+
+/// Elman RNN cell, `h' = tanh(W_ih x + b_ih + W_hh h + b_hh)`.
+pub struct RNNCell {
+    ih: Layer,
+    hh: Layer,
+    input_size: usize,
+    hidden_size: usize,
+}
+
+/**
+GRU cell, with reset gate `r`, update gate `z` and candidate `n`.
+- `r = σ(W_ir x + b_ir + W_hr h + b_hr)`
+- `z = σ(W_iz x + b_iz + W_hz h + b_hz)`
+- `n = tanh(W_in x + b_in + r * (W_hn h + b_hn))`
+- `h' = (1 - z) * n + z * h`
+*/
+pub struct GRUCell {
+    ih: Layer,
+    hh: Layer,
+    input_size: usize,
+    hidden_size: usize,
+}
+
+/**
+LSTM cell, with input gate `i`, forget gate `f`, candidate `g` and output gate `o`.
+- `i, f, o = σ(W_i x + b_i + W_h h + b_h)` and `g = tanh(W_i x + b_i + W_h h + b_h)`
+- `c' = f * c + i * g`
+- `h' = o * tanh(c')`
+*/
+pub struct LSTMCell {
+    ih: Layer,
+    hh: Layer,
+    input_size: usize,
+    hidden_size: usize,
+}
+
+impl RNNCell {
+    pub fn new(input_size: u32, hidden_size: u32) -> RNNCell {
+        let (ih, hh) = gate_layers(input_size, hidden_size, 1);
+        RNNCell {
+            ih,
+            hh,
+            input_size: input_size as usize,
+            hidden_size: hidden_size as usize,
+        }
+    }
+}
+
+impl GRUCell {
+    pub fn new(input_size: u32, hidden_size: u32) -> GRUCell {
+        let (ih, hh) = gate_layers(input_size, hidden_size, 3);
+        GRUCell {
+            ih,
+            hh,
+            input_size: input_size as usize,
+            hidden_size: hidden_size as usize,
+        }
+    }
+}
+
+impl LSTMCell {
+    pub fn new(input_size: u32, hidden_size: u32) -> LSTMCell {
+        let (ih, hh) = gate_layers(input_size, hidden_size, 4);
+        LSTMCell {
+            ih,
+            hh,
+            input_size: input_size as usize,
+            hidden_size: hidden_size as usize,
+        }
+    }
+}
+
+// Input-hidden and hidden-hidden layers, with the gates stacked along the outputs.
+// Weights and biases are drawn from Uniform(-k, k) with k = 1 / √hidden_size.
+fn gate_layers(input_size: u32, hidden_size: u32, gates: u32) -> (Layer, Layer) {
+    let k = 1.0 / (hidden_size as f64).sqrt();
+    let init = Init::Uniform(-k, k);
+
+    (
+        Layer::with_init(input_size, gates * hidden_size, None, &init, &init),
+        Layer::with_init(hidden_size, gates * hidden_size, None, &init, &init),
+    )
+}
+
+impl RecurrentCell for RNNCell {
+    fn input_size(&self) -> usize {
+        self.input_size
+    }
+
+    fn hidden_size(&self) -> usize {
+        self.hidden_size
+    }
+
+    fn init_state(&self) -> RecurrentState {
+        RecurrentState {
+            h: Value::new_1d(&vec![0.0; self.hidden_size]),
+            c: None,
+        }
+    }
+
+    fn step(&self, x: &[Value], state: &RecurrentState) -> RecurrentState {
+        let h = self
+            .ih
+            .forw(x)
+            .iter()
+            .zip(self.hh.forw(&state.h))
+            .map(|(xi, hi)| (xi + hi).tanh())
+            .collect();
+
+        RecurrentState { h, c: None }
+    }
+}
+
+impl RecurrentCell for GRUCell {
+    fn input_size(&self) -> usize {
+        self.input_size
+    }
+
+    fn hidden_size(&self) -> usize {
+        self.hidden_size
+    }
+
+    fn init_state(&self) -> RecurrentState {
+        RecurrentState {
+            h: Value::new_1d(&vec![0.0; self.hidden_size]),
+            c: None,
+        }
+    }
+
+    fn step(&self, x: &[Value], state: &RecurrentState) -> RecurrentState {
+        let n = self.hidden_size;
+        let (xs, hs) = (self.ih.forw(x), self.hh.forw(&state.h));
+        let (xr, xz, xn) = (&xs[..n], &xs[n..2 * n], &xs[2 * n..]);
+        let (hr, hz, hn) = (&hs[..n], &hs[n..2 * n], &hs[2 * n..]);
+
+        let h = (0..n)
+            .map(|j| {
+                let r = (&xr[j] + &hr[j]).sigmoid();
+                let z = (&xz[j] + &hz[j]).sigmoid();
+                let cand = (&xn[j] + r * &hn[j]).tanh();
+                (1.0 - &z) * cand + z * &state.h[j]
+            })
+            .collect();
+
+        RecurrentState { h, c: None }
+    }
+}
+
+impl RecurrentCell for LSTMCell {
+    fn input_size(&self) -> usize {
+        self.input_size
+    }
+
+    fn hidden_size(&self) -> usize {
+        self.hidden_size
+    }
+
+    fn init_state(&self) -> RecurrentState {
+        RecurrentState {
+            h: Value::new_1d(&vec![0.0; self.hidden_size]),
+            c: Some(Value::new_1d(&vec![0.0; self.hidden_size])),
+        }
+    }
+
+    fn step(&self, x: &[Value], state: &RecurrentState) -> RecurrentState {
+        let n = self.hidden_size;
+        let c_prev = state.c.as_ref().expect("LSTMCell state without cell state");
+        let gates: Vec<Value> = self
+            .ih
+            .forw(x)
+            .iter()
+            .zip(self.hh.forw(&state.h))
+            .map(|(xi, hi)| xi + hi)
+            .collect();
+
+        let (h, c) = (0..n)
+            .map(|j| {
+                let i = gates[j].sigmoid();
+                let f = gates[n + j].sigmoid();
+                let g = gates[2 * n + j].tanh();
+                let o = gates[3 * n + j].sigmoid();
+
+                let c = f * &c_prev[j] + i * g;
+                (o * c.tanh(), c)
+            })
+            .unzip();
+
+        RecurrentState { h, c: Some(c) }
+    }
+}
+
+// Module impls of the cells run a single step from the initial state.
+
+macro_rules! impl_cell_module {
+    ($cell:ty, $name:literal) => {
+        impl Module for $cell {
+            fn forw(&self, x: &[Value]) -> Vec<Value> {
+                self.step(x, &self.init_state()).h
+            }
+
+            fn parameters(&self) -> Vec<Value> {
+                let mut params = self.ih.parameters();
+                params.extend(self.hh.parameters());
+                params
+            }
+
+            fn named_parameters(&self) -> Vec<(String, Value)> {
+                let ih = self.ih.named_parameters().into_iter();
+                let hh = self.hh.named_parameters().into_iter();
+                ih.map(|(name, p)| (format!("ih.{}", name), p))
+                    .chain(hh.map(|(name, p)| (format!("hh.{}", name), p)))
+                    .collect()
+            }
+        }
+
+        impl fmt::Display for $cell {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "{}({}) → {}", $name, self.input_size, self.hidden_size)
+            }
+        }
+
+        impl fmt::Debug for $cell {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.debug_struct(stringify!($cell))
+                    .field("input_size", &self.input_size)
+                    .field("hidden_size", &self.hidden_size)
+                    .finish()
+            }
+        }
+    };
+}
+
+impl_cell_module!(RNNCell, "rnn_cell");
+impl_cell_module!(GRUCell, "gru_cell");
+impl_cell_module!(LSTMCell, "lstm_cell");
+
+/**
+Unrolls a `RecurrentCell` over a sequence of time steps, with backpropagation through time.
+With truncation, the state is detached every `k` steps, so that backpropagation through the
+graph of a long sequence goes back at most `k` steps.
+*/
+pub struct Recurrent<C: RecurrentCell> {
+    cell: C,
+    truncation: Option<usize>,
+}
+
+impl<C: RecurrentCell> Recurrent<C> {
+    pub fn new(cell: C) -> Recurrent<C> {
+        Recurrent {
+            cell,
+            truncation: None,
+        }
+    }
+
+    /// Truncated backpropagation through time, detaching the state every `k` steps.
+    pub fn with_truncation(mut self, k: u32) -> Recurrent<C> {
+        assert!(k > 0, "Truncation must be at least 1 step");
+        self.truncation = Some(k as usize);
+        self
+    }
+
+    /**
+    Runs the cell over the time steps `xs`, from `state` or from zeros.
+    Returns the hidden states of all time steps and the final state. The final state can be
+    detached and passed to the next call, to process a long sequence in chunks.
+    */
+    pub fn unroll(
+        &self,
+        xs: &[Vec<Value>],
+        state: Option<RecurrentState>,
+    ) -> (Vec<Vec<Value>>, RecurrentState) {
+        let mut state = state.unwrap_or_else(|| self.cell.init_state());
+        let mut hs = Vec::with_capacity(xs.len());
+
+        for (t, x) in xs.iter().enumerate() {
+            if self
+                .truncation
+                .is_some_and(|k| t > 0 && t.is_multiple_of(k))
+            {
+                state = state.detach();
+            }
+            state = self.cell.step(x, &state);
+            hs.push(state.h.clone());
+        }
+
+        (hs, state)
+    }
+}
+
+impl<C: RecurrentCell> Module for Recurrent<C> {
+    /// Final hidden state of a sequence, flattened time step by time step.
+    fn forw(&self, x: &[Value]) -> Vec<Value> {
+        let input_size = self.cell.input_size();
+        assert!(
+            x.len().is_multiple_of(input_size),
+            "Mismatching input size for Recurrent"
+        );
+
+        let steps: Vec<Vec<Value>> = x.chunks(input_size).map(|step| step.to_vec()).collect();
+        self.unroll(&steps, None).1.h
+    }
+
+    fn parameters(&self) -> Vec<Value> {
+        self.cell.parameters()
+    }
+
+    fn named_parameters(&self) -> Vec<(String, Value)> {
+        self.cell
+            .named_parameters()
+            .into_iter()
+            .map(|(name, p)| (format!("cell.{}", name), p))
+            .collect()
+    }
+
+    fn buffers(&self) -> Vec<Value> {
+        self.cell.buffers()
+    }
+
+    fn set_training(&mut self, training: bool) {
+        self.cell.set_training(training);
+    }
+}
+
+impl<C: RecurrentCell> fmt::Display for Recurrent<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.truncation {
+            Some(k) => write!(f, "recurrent({}, truncation {})", self.cell, k),
+            None => write!(f, "recurrent({})", self.cell),
+        }
+    }
+}
+
+impl<C: RecurrentCell> fmt::Debug for Recurrent<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Recurrent")
+            .field("cell", &self.cell)
+            .field("truncation", &self.truncation)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Cell with a running buffer and a training mode, to check that Recurrent forwards both.
+    #[derive(Debug)]
+    struct ModeCell {
+        training: bool,
+        running: Value,
+    }
+
+    impl Module for ModeCell {
+        fn forw(&self, x: &[Value]) -> Vec<Value> {
+            self.step(x, &self.init_state()).h
+        }
+
+        fn parameters(&self) -> Vec<Value> {
+            vec![]
+        }
+
+        fn buffers(&self) -> Vec<Value> {
+            vec![self.running.clone()]
+        }
+
+        fn set_training(&mut self, training: bool) {
+            self.training = training;
+        }
+    }
+
+    impl fmt::Display for ModeCell {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "mode_cell")
+        }
+    }
+
+    impl RecurrentCell for ModeCell {
+        fn input_size(&self) -> usize {
+            1
+        }
+
+        fn hidden_size(&self) -> usize {
+            1
+        }
+
+        fn init_state(&self) -> RecurrentState {
+            RecurrentState {
+                h: vec![Value::new(0.0)],
+                c: None,
+            }
+        }
+
+        fn step(&self, x: &[Value], state: &RecurrentState) -> RecurrentState {
+            let scale = if self.training { 1.0 } else { 0.0 };
+            RecurrentState {
+                h: vec![&state.h[0] + &(&x[0] * scale)],
+                c: None,
+            }
+        }
+    }
+
+    #[test]
+    fn forwards_mode_and_buffers_to_cell() {
+        let mut rnn = Recurrent::new(ModeCell {
+            training: true,
+            running: Value::new(0.5),
+        });
+        let x = Value::new_1d(&[1.0, 2.0]);
+        assert_eq!(rnn.forw(&x)[0].borrow().data, 3.0);
+
+        rnn.eval();
+        assert_eq!(rnn.forw(&x)[0].borrow().data, 0.0);
+
+        rnn.train();
+        assert_eq!(rnn.forw(&x)[0].borrow().data, 3.0);
+
+        let buffers = rnn.buffers();
+        assert_eq!(buffers.len(), 1);
+        assert_eq!(buffers[0].borrow().data, 0.5);
+    }
+}
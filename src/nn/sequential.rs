@@ -0,0 +1,92 @@
+use crate::engine::Value;
+use crate::nn::Module;
+use std::fmt;
+
+/// Chains modules, feeding the output of each one into the next.
+pub struct Sequential {
+    modules: Vec<Box<dyn Module>>,
+}
+
+impl Sequential {
+    pub fn new() -> Sequential {
+        Sequential { modules: vec![] }
+    }
+
+    /// Append a module to the end of the chain.
+    pub fn push<M: Module + 'static>(mut self, module: M) -> Sequential {
+        self.modules.push(Box::new(module));
+        self
+    }
+}
+
+impl Default for Sequential {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Module for Sequential {
+    /// Forward pass of a single 1d input x through the modules.
+    fn forw(&self, x: &[Value]) -> Vec<Value> {
+        self.modules
+            .iter()
+            .fold(x.to_vec(), |x, module| module.forw(&x))
+    }
+
+    /// Forward pass of 2d input x through the modules.
+    fn forward(&self, x: &[Vec<Value>]) -> Vec<Vec<Value>> {
+        self.modules
+            .iter()
+            .fold(x.to_vec(), |x, module| module.forward(&x))
+    }
+
+    fn parameters(&self) -> Vec<Value> {
+        self.modules.iter().flat_map(|m| m.parameters()).collect()
+    }
+
+    fn buffers(&self) -> Vec<Value> {
+        self.modules.iter().flat_map(|m| m.buffers()).collect()
+    }
+
+    fn named_parameters(&self) -> Vec<(String, Value)> {
+        self.modules
+            .iter()
+            .enumerate()
+            .flat_map(|(i, m)| {
+                m.named_parameters()
+                    .into_iter()
+                    .map(move |(name, p)| (format!("module{}.{}", i, name), p))
+            })
+            .collect()
+    }
+
+    fn set_training(&mut self, training: bool) {
+        for module in self.modules.iter_mut() {
+            module.set_training(training);
+        }
+    }
+}
+
+impl fmt::Display for Sequential {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Sequential:")?;
+        for (i, module) in self.modules.iter().enumerate() {
+            // Indent the lines of nested containers.
+            let module = module.to_string();
+            let module = module.trim_end().replace('\n', "\n  ");
+            writeln!(f, "module {} - {}", i, module)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for Sequential {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut debug_struct = f.debug_struct("Sequential");
+        debug_struct.field("parameters", &self.parameters().len());
+        for (i, module) in self.modules.iter().enumerate() {
+            debug_struct.field(&format!("module {}", i), module);
+        }
+        debug_struct.finish()
+    }
+}
@@ -0,0 +1,49 @@
+use crate::engine::Value;
+
+/// Asserts that the gradients of `inputs` from backpropagating `f()` are within `tolerance` of
+/// central finite differences.
+pub(crate) fn assert_gradients<F>(f: F, inputs: &[Value], tolerance: f64)
+where
+    F: Fn() -> Value,
+{
+    let h = 1e-6;
+
+    for x in inputs {
+        x.borrow_mut().grad = 0.0;
+    }
+    f().backward();
+
+    for (i, x) in inputs.iter().enumerate() {
+        let data = x.borrow().data;
+
+        x.borrow_mut().data = data + h;
+        let plus = f().borrow().data;
+        x.borrow_mut().data = data - h;
+        let minus = f().borrow().data;
+        x.borrow_mut().data = data;
+
+        let (numerical, grad) = ((plus - minus) / (2.0 * h), x.borrow().grad);
+        assert!(
+            (numerical - grad).abs() <= tolerance,
+            "Gradient of input {} is {}, finite differences give {}",
+            i,
+            grad,
+            numerical
+        );
+    }
+}
+
+/// Distinct inputs in [-1, 1], so that maxima are unique.
+pub(crate) fn test_inputs(n: usize) -> Vec<Value> {
+    (0..n)
+        .map(|i| Value::new((i as f64 * 1.7 + 0.3).sin()))
+        .collect()
+}
+
+/// Sum of `ys` with distinct coefficients, to reduce the outputs of a module to a scalar.
+pub(crate) fn weighted_sum(ys: &[Value]) -> Value {
+    ys.iter()
+        .enumerate()
+        .map(|(i, y)| y * (0.5 + 0.25 * i as f64))
+        .sum()
+}
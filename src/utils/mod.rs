@@ -1,4 +1,10 @@
 mod csv;
+#[cfg(test)]
+mod gradcheck;
+mod rng;
 mod save;
 
 pub use csv::*;
+#[cfg(test)]
+pub(crate) use gradcheck::*;
+pub use rng::*;
@@ -0,0 +1,37 @@
+use crate::engine::Value;
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+use std::cell::RefCell;
+
+// Source of randomness for weight initialisation, dropout, shuffling and sampling.
+thread_local! {
+    static RNG: RefCell<StdRng> = RefCell::new(StdRng::from_entropy());
+}
+
+/// Seed the random number generator used by the library, to make runs reproducible.
+pub fn manual_seed(seed: u64) {
+    RNG.with(|rng| *rng.borrow_mut() = StdRng::seed_from_u64(seed));
+}
+
+/// Run `f` with the random number generator used by the library.
+pub fn with_rng<T, F: FnOnce(&mut StdRng) -> T>(f: F) -> T {
+    RNG.with(|rng| f(&mut rng.borrow_mut()))
+}
+
+/// Shuffle the samples `xs` and the targets `ys`, keeping the pairs together.
+pub fn shuffle(xs: &mut [Vec<Value>], ys: &mut [Vec<Value>]) {
+    assert!(xs.len() == ys.len(), "Mismatching number of samples");
+
+    let mut indices: Vec<usize> = (0..xs.len()).collect();
+    with_rng(|rng| indices.shuffle(rng));
+
+    let (xs_old, ys_old) = (xs.to_vec(), ys.to_vec());
+    for (i, j) in indices.into_iter().enumerate() {
+        xs[i] = xs_old[j].clone();
+        ys[i] = ys_old[j].clone();
+    }
+}
+
+/// Indices of `n` samples drawn without replacement from `0..len`.
+pub fn sample_indices(len: usize, n: usize) -> Vec<usize> {
+    with_rng(|rng| rand::seq::index::sample(rng, len, n).into_vec())
+}
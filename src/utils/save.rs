@@ -1,42 +1,62 @@
-use crate::nn::MultiLayerPerceptron;
+use crate::engine::Value;
+use crate::nn::{Module, MultiLayerPerceptron, Sequential};
 use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
 use std::{fs::File, io::Result, path::Path};
 
 // Modified from https://gist.github.com/rust-play/e710e311a2ad808b5a8789d5e4457426
 
 impl MultiLayerPerceptron {
-    /// Save model weights at the given filepath.
+    /// Save model weights, followed by buffers such as running statistics, at the given filepath.
     pub fn save(&self, filepath: &str) -> Result<()> {
-        let mut file = File::create(filepath)?;
-        let model_weights = self
-            .parameters()
-            .into_iter()
-            .map(|value| value.borrow().data);
+        save_values(state(self), filepath)
+    }
 
-        for weight in model_weights {
-            file.write_f64::<BigEndian>(weight)?;
-        }
+    /// Read model weights and buffers from the given filepath and load state.
+    pub fn load<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        load_values(state(self), path)
+    }
+}
 
-        Ok(())
+impl Sequential {
+    /// Save model weights, followed by buffers such as running statistics, at the given filepath.
+    pub fn save(&self, filepath: &str) -> Result<()> {
+        save_values(state(self), filepath)
     }
 
-    /// Read model weights from the given filepath and load state.
+    /// Read model weights and buffers from the given filepath and load state.
     pub fn load<P: AsRef<Path>>(&self, path: P) -> Result<()> {
-        let mut file = File::open(path)?;
-        let buf_len: usize = file.metadata()?.len() as usize / 8; // 8 bytes for one f64
-        let mut buf: Vec<f64> = vec![0.0; buf_len];
-        file.read_f64_into::<BigEndian>(&mut buf)?;
-
-        let params = self.parameters();
-        assert!(
-            buf_len == self.parameters().len(),
-            "Mismatching number of parameters"
-        );
-
-        for (value, weight) in params.into_iter().zip(buf) {
-            value.borrow_mut().data = weight
-        }
-
-        Ok(())
+        load_values(state(self), path)
+    }
+}
+
+fn state<M: Module>(model: &M) -> Vec<Value> {
+    let mut values = model.parameters();
+    values.extend(model.buffers());
+    values
+}
+
+fn save_values(values: Vec<Value>, filepath: &str) -> Result<()> {
+    let mut file = File::create(filepath)?;
+    let model_weights = values.into_iter().map(|value| value.borrow().data);
+
+    for weight in model_weights {
+        file.write_f64::<BigEndian>(weight)?;
     }
+
+    Ok(())
+}
+
+fn load_values<P: AsRef<Path>>(values: Vec<Value>, path: P) -> Result<()> {
+    let mut file = File::open(path)?;
+    let buf_len: usize = file.metadata()?.len() as usize / 8; // 8 bytes for one f64
+    let mut buf: Vec<f64> = vec![0.0; buf_len];
+    file.read_f64_into::<BigEndian>(&mut buf)?;
+
+    assert!(buf_len == values.len(), "Mismatching number of parameters");
+
+    for (value, weight) in values.into_iter().zip(buf) {
+        value.borrow_mut().data = weight
+    }
+
+    Ok(())
 }